@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+
+
+/// Represents a fixed-size board with blocked cells (for an image or a logo) on which [words](Word) are placed at absolute coordinates
+///
+/// A board can be a torus (see [new_torus](Board::new_torus)): a word that runs off one edge continues on the opposite edge.
+///
+/// ## Example
+///
+/// ```text
+/// . . . . .      . -> free cell
+/// . # # . .      # -> blocked cell
+/// . . . . .
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Board
+{
+    pub width: usize,
+    pub height: usize,
+    /// Blocked cells as (x, y)
+    #[serde(default)]
+    pub blocked: BTreeSet<(usize, usize)>,
+    /// If true, positions are taken modulo the size of the board
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub wrap: bool
+}
+
+impl Board
+{
+    /// Creates a board of the given size without blocked cells
+    pub fn new(width: usize, height: usize) -> Board
+    {
+        Board { width, height, blocked: BTreeSet::new(), wrap: false }
+    }
+
+    /// Creates a torus board of the given size without blocked cells, words wrap around its edges
+    ///
+    /// Words start on the board and their cells are taken modulo the size of the board, a word can't be longer than the board along the axes it runs on.
+    /// Intersections and touching are checked with the other words moved by the width and the height of the board,
+    /// so a word at the right edge touches a word at the left edge.
+    /// The constraints of [CrosswordSettings] see the words at their unwrapped coordinates, so
+    /// [validate](crate::generator::CrosswordGeneratorSettings::validate) rejects most of them on a torus.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
+    /// # use crossword_generator::board::Board;
+    /// let board = Board::new_torus(4, 3);
+    /// let word = Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0};  // . . . .
+    ///                                                                                                                // t . c a
+    /// assert!(board.is_word_allowed(&word));                                                                         // . . . .
+    /// assert_eq!(board.get_wrapped_cells(&word)[2], (WordPosition { x: 0, y: 1 }, 't'));
+    ///
+    /// let top = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "at", id: 0};
+    /// assert!(board.are_words_compatible(&word, &top, &WordCompatibilitySettings::default()));
+    /// assert!(!board.are_words_compatible(&word, &Word{ value: "an", ..top }, &WordCompatibilitySettings::default()));
+    /// ```
+    pub fn new_torus(width: usize, height: usize) -> Board
+    {
+        Board { width, height, blocked: BTreeSet::new(), wrap: true }
+    }
+
+    /// Returns the position on the board, taken modulo its size if it [wraps](Board::wrap)
+    pub fn wrap_position(&self, position: &WordPosition) -> WordPosition
+    {
+        if !self.wrap || self.width == 0 || self.height == 0 { return position.clone(); }
+        WordPosition { x: position.x.rem_euclid(self.width as isize), y: position.y.rem_euclid(self.height as isize) }
+    }
+
+    /// Returns the [cells](Word::get_cells) of the [word](Word) with [wrapped](Board::wrap_position) positions
+    pub fn get_wrapped_cells(&self, word: &Word) -> Vec<(WordPosition, char)>
+    {
+        word.get_cells().into_iter().map(|(position, ch)| (self.wrap_position(&position), ch)).collect()
+    }
+
+    /// Returns the [word](Word) and its copies moved by the width and the height of the board, only the word itself if the board doesn't [wrap](Board::wrap)
+    ///
+    /// One period on every axis is enough for the words that [are allowed](Board::is_word_allowed) on the board.
+    fn get_shifted_copies<'a>(&self, word: &Word<'a>) -> Vec<Word<'a>>
+    {
+        if !self.wrap { return vec![word.clone()]; }
+        let (width, height) = (self.width as isize, self.height as isize);
+        (-1..=1)
+            .flat_map(|kx| (-1..=1).map(move |ky| (kx, ky)))
+            .map(|(kx, ky)| Word{ position: WordPosition { x: word.position.x + kx * width, y: word.position.y + ky * height }, ..word.clone() })
+            .collect()
+    }
+
+    /// Checks if two [words](Word) are compatible on the board, on a torus also across its edges
+    pub fn are_words_compatible<C: WordCompatibility + ?Sized>(&self, first: &Word, second: &Word, word_compatibility: &C) -> bool
+    {
+        self.get_shifted_copies(second).iter().all(|copy| word_compatibility.are_words_compatible(first, copy))
+    }
+
+    /// Checks if two [words](Word) cross each other on the board, on a torus also across its edges
+    pub fn do_words_cross(&self, first: &Word, second: &Word) -> bool
+    {
+        self.get_shifted_copies(second).iter().any(|copy| first.get_intersection_indices(copy).is_some())
+    }
+
+    /// Checks if the [word](Word) doesn't touch itself across the edges of a torus, for example a word as long as the width in a row touches itself head by head
+    fn is_word_compatible_with_itself<C: WordCompatibility + ?Sized>(&self, word: &Word, word_compatibility: &C) -> bool
+    {
+        self.get_shifted_copies(word).iter().filter(|copy| *copy != word).all(|copy| word_compatibility.are_words_compatible(word, copy))
+    }
+
+    /// Reads a board from ASCII art, one line per row, '#' is a blocked cell and any other character is a free cell
+    ///
+    /// The width is the length of the longest line, missing cells of shorter lines are free. Leading and trailing empty lines are ignored.
+    pub fn from_ascii(board: &str) -> Board
+    {
+        let lines: Vec<&str> = board.lines().collect();
+        let first = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(lines.len());
+        let last = lines.iter().rposition(|l| !l.trim().is_empty()).map_or(first, |l| l + 1);
+        let lines = &lines[first..last];
+
+        let blocked = lines.iter().enumerate()
+            .flat_map(|(y, line)| line.chars().enumerate().filter(|(_, ch)| *ch == '#').map(move |(x, _)| (x, y)))
+            .collect();
+
+        Board
+        {
+            width: lines.iter().map(|l| l.chars().count()).max().unwrap_or(0),
+            height: lines.len(),
+            blocked,
+            wrap: false
+        }
+    }
+
+    /// Checks if the cell is on the board and not blocked, the position is [wrapped](Board::wrap_position) first
+    pub fn is_cell_free(&self, position: &WordPosition) -> bool
+    {
+        let position = self.wrap_position(position);
+        position.x >= 0 && position.y >= 0 &&
+        (position.x as usize) < self.width && (position.y as usize) < self.height &&
+        !self.blocked.contains(&(position.x as usize, position.y as usize))
+    }
+
+    /// Checks if all cells of the [word](Word) are free
+    ///
+    /// On a torus the word has to start on the board and can't be longer than the width if it runs across or than the height if it runs down,
+    /// a diagonal word is limited by both. So a word goes around the torus at most once on every axis, and the copies of the words
+    /// moved by one width and one height are enough to find all their intersections and touches.
+    pub fn is_word_allowed(&self, word: &Word) -> bool
+    {
+        if self.wrap
+        {
+            let (dx, dy) = word.direction.get_step();
+            let length = word.value.chars().count();
+            if (dx != 0 && length > self.width) || (dy != 0 && length > self.height) { return false; }
+            if self.wrap_position(&word.position) != word.position { return false; }
+        }
+        word.get_cells().iter().all(|(position, _)| self.is_cell_free(position))
+    }
+
+    /// Returns all placements of the word on free cells of the board, in the given directions
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition, DEFAULT_DIRECTIONS};
+    /// # use crossword_generator::board::Board;
+    /// let board = Board::from_ascii("\
+    /// ..
+    /// .#");
+    ///
+    /// assert_eq!(board.get_placements("ab", &DEFAULT_DIRECTIONS).into_iter().collect::<Vec<_>>(), vec![
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "ab", id: 0},
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "ab", id: 0},
+    /// ]);
+    /// ```
+    pub fn get_placements<'a>(&self, word: &'a str, directions: &[WordDirection]) -> BTreeSet<Word<'a>>
+    {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| directions.iter().map(move |direction| Word{ position: WordPosition { x: x as isize, y: y as isize }, direction: direction.clone(), value: word, id: 0 }))
+            .filter(|w| self.is_word_allowed(w))
+            .collect()
+    }
+
+    /// Returns all possible ways to add a word into the [absolute](Crossword::new_absolute) [crossword](Crossword) on free cells of the board, in the given directions
+    ///
+    /// The first word can be anywhere on the board, next words are placed like with [Crossword::calculate_possible_ways_to_add_word_in_directions].
+    /// On a torus, next words are the placements that [cross](Board::do_words_cross) a word of the crossword and are [compatible](Board::are_words_compatible) with all of them.
+    pub fn calculate_possible_ways_to_add_word<'a, C: WordCompatibility + ?Sized>(&self, cw: &Crossword<'a>, word: &'a str, word_compatibility: &C, directions: &[WordDirection]) -> BTreeSet<Word<'a>>
+    {
+        if self.wrap
+        {
+            return self.get_placements(word, directions).into_iter()
+                .filter(|w| self.is_word_compatible_with_itself(w, word_compatibility))
+                .filter(|w| cw.get_words().is_empty() || cw.get_words().iter().any(|other| self.do_words_cross(other, w)))
+                .filter(|w| cw.get_words().iter().all(|other| self.are_words_compatible(other, w, word_compatibility)))
+                .collect();
+        }
+
+        if cw.get_words().is_empty()
+        {
+            return self.get_placements(word, directions);
+        }
+
+        cw.calculate_possible_ways_to_add_word_in_directions(word, word_compatibility, directions).into_iter()
+            .filter(|w| self.is_word_allowed(w))
+            .collect()
+    }
+
+    /// Returns the number of [words](Word) of the [crossword](Crossword) that [cross](Board::do_words_cross) the word on the board, like [Crossword::get_crossing_count]
+    pub fn get_crossing_count(&self, cw: &Crossword, word: &Word) -> usize
+    {
+        cw.get_words().iter().filter(|w| self.do_words_cross(w, word)).count()
+    }
+
+    /// Returns the number of [wrapped](Board::get_wrapped_cells) cells shared by two or more [words](Word) of the [crossword](Crossword), like [Crossword::get_checked_cell_count]
+    pub fn get_checked_cell_count(&self, cw: &Crossword) -> usize
+    {
+        let mut counts: BTreeMap<WordPosition, usize> = BTreeMap::new();
+        for (position, _) in cw.get_words().iter().flat_map(|w| self.get_wrapped_cells(w))
+        {
+            *counts.entry(position).or_default() += 1;
+        }
+        counts.values().filter(|count| **count > 1).count()
+    }
+
+    /// Returns a matrix of characters of the board with the letters of the [crossword](Crossword) on their [wrapped](Board::wrap_position) cells,
+    /// '#' on blocked cells and ' ' on free cells without letters
+    pub fn generate_char_table(&self, cw: &Crossword) -> Vec<Vec<char>>
+    {
+        let mut table = vec![vec![' '; self.width]; self.height];
+        for (x, y) in self.blocked.iter().filter(|(x, y)| *x < self.width && *y < self.height)
+        {
+            table[*y][*x] = '#';
+        }
+        for word in cw.get_words()
+        {
+            for (position, ch) in self.get_wrapped_cells(word)
+            {
+                if self.is_cell_free(&position)
+                {
+                    table[position.y as usize][position.x as usize] = ch;
+                }
+            }
+        }
+
+        table
+    }
+
+    /// Returns a printable [String] of the [char table](Board::generate_char_table), boxed like [Crossword::generate_string]
+    pub fn generate_string(&self, cw: &Crossword) -> String
+    {
+        format_char_table(self.generate_char_table(cw))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_board_from_ascii() {
+        let board = Board::from_ascii("\n....\n.##\n\n");
+        assert_eq!(board, Board { width: 4, height: 2, blocked: BTreeSet::from([(1, 1), (2, 1)]), wrap: false });
+
+        assert!(board.is_cell_free(&WordPosition { x: 3, y: 1 }));
+        assert!(!board.is_cell_free(&WordPosition { x: 1, y: 1 }));
+        assert!(!board.is_cell_free(&WordPosition { x: 4, y: 0 }));
+        assert!(!board.is_cell_free(&WordPosition { x: 0, y: -1 }));
+    }
+
+    #[test]
+    fn test_board_calculate_possible_ways_to_add_word() {
+        let board = Board::from_ascii("\
+.....
+.#...
+.....");
+
+        assert_eq!(board.get_placements("hello", &DEFAULT_DIRECTIONS).len(), 2);
+        assert_eq!(board.get_placements("ok", &DEFAULT_DIRECTIONS).len(), 10 + 8);
+        assert_eq!(board.get_placements("ok", &[WordDirection::Left, WordDirection::Up]).len(), 10 + 8);
+
+        let cw = Crossword::new_absolute(&[Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0}]);
+        assert_eq!(board.calculate_possible_ways_to_add_word(&cw, "lot", &WordCompatibilitySettings::default(), &DEFAULT_DIRECTIONS), BTreeSet::from([
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "lot", id: 0},
+            Word{position: WordPosition { x: 3, y: 0 }, direction: WordDirection::Down, value: "lot", id: 0},
+        ]));
+        assert_eq!(board.calculate_possible_ways_to_add_word(&cw, "eat", &WordCompatibilitySettings::default(), &DEFAULT_DIRECTIONS), BTreeSet::new());
+    }
+
+    #[test]
+    fn test_torus_board() {
+        let board = Board::new_torus(4, 3);
+        assert_eq!(board.wrap_position(&WordPosition { x: -1, y: 7 }), WordPosition { x: 3, y: 1 });
+        assert!(board.is_cell_free(&WordPosition { x: -1, y: 7 }));
+        assert!(!board.is_word_allowed(&Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Right, value: "ab", id: 0}));
+        assert!(!board.is_word_allowed(&Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "abcd", id: 0}));
+        assert!(board.is_word_allowed(&Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "abcd", id: 0}));
+
+        let narrow = Board::new_torus(3, 2);
+        let diagonal = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::DownRight, value: "abcdef", id: 0};
+        assert_eq!(narrow.get_wrapped_cells(&diagonal).into_iter().map(|(position, _)| position).collect::<BTreeSet<_>>().len(), 6);
+        assert!(!narrow.is_word_allowed(&diagonal));
+        assert!(!narrow.is_word_allowed(&Word{ value: "abc", ..diagonal.clone() }));
+        assert!(narrow.is_word_allowed(&Word{ value: "ab", ..diagonal }));
+
+        assert_eq!(board.get_placements("cats", &DEFAULT_DIRECTIONS).len(), 12);
+        let cw = Crossword::new_absolute(&[]);
+        assert_eq!(board.calculate_possible_ways_to_add_word(&cw, "cats", &WordCompatibilitySettings::default(), &DEFAULT_DIRECTIONS), BTreeSet::new());
+        assert_eq!(board.calculate_possible_ways_to_add_word(&cw, "cat", &WordCompatibilitySettings::default(), &DEFAULT_DIRECTIONS).len(), 12);
+
+        let cw = Crossword::new_absolute(&[Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0}]);
+        let across_edge = Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Down, value: "dog", id: 0};
+        assert!(WordCompatibilitySettings::default().are_words_compatible(cw.get_words().first().unwrap(), &across_edge));
+        assert!(!board.are_words_compatible(cw.get_words().first().unwrap(), &across_edge, &WordCompatibilitySettings::default()));
+        assert_eq!(board.calculate_possible_ways_to_add_word(&cw, "to", &WordCompatibilitySettings::default(), &DEFAULT_DIRECTIONS), BTreeSet::from([
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "to", id: 0},
+        ]));
+    }
+
+    #[test]
+    fn test_torus_board_counts() {
+        let board = Board::new_torus(6, 4);
+        let hello = Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0};
+        let cw = Crossword::new_absolute(&[
+            hello.clone(),
+            Word{position: WordPosition { x: 2, y: 3 }, direction: WordDirection::Down, value: "lot", id: 0},
+        ]);
+
+        assert_eq!(cw.get_crossing_count(&hello), 0);
+        assert_eq!(board.get_crossing_count(&cw, &hello), 1);
+        assert_eq!(cw.get_checked_cell_count(), 0);
+        assert_eq!(board.get_checked_cell_count(&cw), 1);
+        assert_eq!(board.generate_string(&cw), "-------------\n|l l o   h e|\n|    t      |\n|           |\n|    l      |\n-------------\n");
+    }
+}
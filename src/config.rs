@@ -0,0 +1,458 @@
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+use super::generator::*;
+use super::constraint::*;
+use super::board::Board;
+
+
+/// Names of the built-in presets accepted by [CrosswordGeneratorSettings::preset]
+pub const PRESET_NAMES: [&str; 3] = ["default", "newspaper-compact", "kids-loose"];
+
+/// Represents an error in [generator settings](CrosswordGeneratorSettings), either while loading them or while validating them
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum SettingsError
+{
+    /// The settings file can't be read
+    Io(String),
+    /// The settings can't be parsed, or have unknown keys or values of wrong types
+    Parse(String),
+    /// There is no preset with the given name (see [PRESET_NAMES])
+    UnknownPreset(String),
+    /// A size constraint can't be satisfied by any crossword, for example MaxLength(0)
+    UnsatisfiableConstraint(CrosswordSizeConstraint),
+    /// A word of the generator is empty
+    EmptyWord,
+    /// A word of the generator does not fit in the crossword in any direction because of the size constraints
+    WordDoesNotFit{ word: String, constraint: CrosswordSizeConstraint },
+    /// A word of the generator can't be placed on free cells of the [board](crate::board::Board)
+    WordDoesNotFitBoard(String),
+    /// A [word pair constraint](crate::constraint::WordPairConstraint) names a word that is not a word of the generator
+    UnknownPairWord(String),
+    /// A constraint is used with a [board](crate::board::Board) that [wraps](crate::board::Board::wrap), but it checks the words at their unwrapped coordinates
+    ConstraintOnTorus(String),
+}
+
+impl std::fmt::Display for SettingsError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            SettingsError::Io(e) => write!(f, "can't read the settings: {}", e),
+            SettingsError::Parse(e) => write!(f, "can't parse the settings: {}", e),
+            SettingsError::UnknownPreset(name) => write!(f, "unknown preset \"{}\", known presets are: {}", name, PRESET_NAMES.join(", ")),
+            SettingsError::UnsatisfiableConstraint(c) => write!(f, "the size constraint {:?} can't be satisfied by any crossword", c),
+            SettingsError::EmptyWord => write!(f, "the word list contains an empty word"),
+            SettingsError::WordDoesNotFit{ word, constraint } => write!(f, "the word \"{}\" does not fit in any direction because of the size constraint {:?}", word, constraint),
+            SettingsError::WordDoesNotFitBoard(word) => write!(f, "the word \"{}\" can't be placed on free cells of the board", word),
+            SettingsError::UnknownPairWord(word) => write!(f, "the word pair constraint names the word \"{}\" that is not in the word list", word),
+            SettingsError::ConstraintOnTorus(constraint) => write!(f, "the constraint {} can't be used on a torus board, it doesn't see the words wrapped around the edges", constraint),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// Checks if the constraint gives the right answer on a torus board: custom constraints are left to their authors, the built-in ones only see unwrapped coordinates
+fn is_checked_on_torus(constraint: &CombinedConstraint) -> bool
+{
+    match constraint
+    {
+        CombinedConstraint::And(constraints) | CombinedConstraint::Or(constraints) => constraints.iter().all(is_checked_on_torus),
+        CombinedConstraint::Not(constraint) => is_checked_on_torus(constraint),
+        CombinedConstraint::Custom(_) => true,
+        _ => false
+    }
+}
+
+/// Deep merges `overrides` into `base`, objects are merged key by key, everything else is replaced
+fn merge_json(base: &mut serde_json::Value, overrides: serde_json::Value)
+{
+    match (base, overrides)
+    {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) =>
+        {
+            for (key, value) in overrides
+            {
+                match base.get_mut(&key)
+                {
+                    Some(base_value) => merge_json(base_value, value),
+                    None => { base.insert(key, value); }
+                }
+            }
+        },
+        (base, overrides) => *base = overrides
+    }
+}
+
+/// Returns the first key of `value` that `reference` doesn't have, as a path of keys joined with '.'
+///
+/// Objects are checked key by key, lists and other values are not looked into, their types check their own fields.
+fn find_unknown_key(value: &serde_json::Value, reference: &serde_json::Value, path: &str) -> Option<String>
+{
+    let (serde_json::Value::Object(value), serde_json::Value::Object(reference)) = (value, reference) else { return None; };
+
+    value.iter().find_map(|(key, value)|
+    {
+        let key_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+        match reference.get(key)
+        {
+            Some(reference) => find_unknown_key(value, reference, &key_path),
+            None => Some(key_path)
+        }
+    })
+}
+
+impl CrosswordGeneratorSettings
+{
+    /// Returns the built-in preset with the given name
+    ///
+    /// ## Presets
+    ///
+    /// default -> [CrosswordGeneratorSettings::default]
+    ///
+    /// newspaper-compact -> dense crosswords that fit a 13x13 newspaper column: words can touch side by head and corner by corner
+    ///
+    /// kids-loose -> airy crosswords for children up to 20x20: words can't touch each other in any way, not even corner by corner
+    pub fn preset(name: &str) -> Result<CrosswordGeneratorSettings, SettingsError>
+    {
+        match name
+        {
+            "default" => Ok(CrosswordGeneratorSettings::default()),
+            "newspaper-compact" => Ok(CrosswordGeneratorSettings
+            {
+                word_compatibility_settings: WordCompatibilitySettings { side_by_side: false, head_by_head: false, side_by_head: true, corner_by_corner: true },
+                crossword_settings: CrosswordSettings { size_constraints: vec![CrosswordSizeConstraint::MaxLength(13), CrosswordSizeConstraint::MaxHeight(13)], ..Default::default() },
+                ..Default::default()
+            }),
+            "kids-loose" => Ok(CrosswordGeneratorSettings
+            {
+                word_compatibility_settings: WordCompatibilitySettings { side_by_side: false, head_by_head: false, side_by_head: false, corner_by_corner: false },
+                crossword_settings: CrosswordSettings { size_constraints: vec![CrosswordSizeConstraint::MaxLength(20), CrosswordSizeConstraint::MaxHeight(20)], ..Default::default() },
+                ..Default::default()
+            }),
+            _ => Err(SettingsError::UnknownPreset(name.to_owned()))
+        }
+    }
+
+    /// Builds settings from a parsed settings document
+    ///
+    /// If the document has a "preset" key, the [preset](CrosswordGeneratorSettings::preset) is used as a base, otherwise the default settings are.
+    /// The other keys of the document override the base, objects key by key (so a single compatibility setting can be changed), lists as a whole.
+    /// Keys that the settings don't have, for example misspelled ones, are rejected with [SettingsError::Parse].
+    fn from_json_value(mut value: serde_json::Value) -> Result<CrosswordGeneratorSettings, SettingsError>
+    {
+        let preset = match value.as_object_mut().and_then(|o| o.remove("preset"))
+        {
+            Some(serde_json::Value::String(name)) => CrosswordGeneratorSettings::preset(&name)?,
+            Some(other) => return Err(SettingsError::Parse(format!("preset must be a string, found {}", other))),
+            None => CrosswordGeneratorSettings::default()
+        };
+
+        // optional fields are set, so that all the keys are serialized
+        let reference = CrosswordGeneratorSettings { board: Some(Board { wrap: true, ..Default::default() }), ..Default::default() };
+        let reference = serde_json::to_value(reference).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        if let Some(key) = find_unknown_key(&value, &reference, "")
+        {
+            return Err(SettingsError::Parse(format!("unknown key \"{}\"", key)));
+        }
+
+        let mut base = serde_json::to_value(preset).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        merge_json(&mut base, value);
+
+        let settings: CrosswordGeneratorSettings = serde_json::from_value(base).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        settings.validate()?;
+
+        Ok(settings)
+    }
+
+    /// Parses and [validates](CrosswordGeneratorSettings::validate) settings written in JSON
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::generator::CrosswordGeneratorSettings;
+    /// # use crossword_generator::crossword::CrosswordSizeConstraint;
+    /// let settings = CrosswordGeneratorSettings::from_json_str(r#"
+    /// {
+    ///     "preset": "newspaper-compact",
+    ///     "word_compatibility_settings": { "corner_by_corner": false },
+    ///     "crossword_settings": { "size_constraints": [{ "MaxArea": 100 }] }
+    /// }"#).unwrap();
+    ///
+    /// assert!(settings.word_compatibility_settings.side_by_head);
+    /// assert!(!settings.word_compatibility_settings.corner_by_corner);
+    /// assert_eq!(settings.crossword_settings.size_constraints, vec![CrosswordSizeConstraint::MaxArea(100)]);
+    /// ```
+    pub fn from_json_str(settings: &str) -> Result<CrosswordGeneratorSettings, SettingsError>
+    {
+        let value: serde_json::Value = serde_json::from_str(settings).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        CrosswordGeneratorSettings::from_json_value(value)
+    }
+
+    /// Parses and [validates](CrosswordGeneratorSettings::validate) settings written in TOML
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::generator::CrosswordGeneratorSettings;
+    /// # use crossword_generator::crossword::CrosswordSizeConstraint;
+    /// let settings = CrosswordGeneratorSettings::from_toml_str(r#"
+    /// preset = "kids-loose"
+    ///
+    /// [crossword_settings]
+    /// size_constraints = [{ MaxLength = 10 }, { MaxHeight = 8 }]
+    /// "#).unwrap();
+    ///
+    /// assert!(!settings.word_compatibility_settings.corner_by_corner);
+    /// assert_eq!(settings.crossword_settings.size_constraints, vec![CrosswordSizeConstraint::MaxLength(10), CrosswordSizeConstraint::MaxHeight(8)]);
+    /// ```
+    pub fn from_toml_str(settings: &str) -> Result<CrosswordGeneratorSettings, SettingsError>
+    {
+        let value: serde_json::Value = toml::from_str(settings).map_err(|e| SettingsError::Parse(e.to_string()))?;
+        CrosswordGeneratorSettings::from_json_value(value)
+    }
+
+    /// Reads settings from a file, the format is chosen by the extension: ".toml" for TOML and anything else for JSON
+    pub fn from_file(path: impl AsRef<Path>) -> Result<CrosswordGeneratorSettings, SettingsError>
+    {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| SettingsError::Io(format!("{}: {}", path.display(), e)))?;
+
+        let result = match path.extension().and_then(|e| e.to_str())
+        {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => CrosswordGeneratorSettings::from_toml_str(&content),
+            _ => CrosswordGeneratorSettings::from_json_str(&content)
+        };
+
+        result.map_err(|e| match e
+        {
+            SettingsError::Parse(e) => SettingsError::Parse(format!("{}: {}", path.display(), e)),
+            e => e
+        })
+    }
+
+    /// Checks that the settings make sense, for example that no size constraint is impossible to satisfy
+    ///
+    /// On a [board](CrosswordGeneratorSettings::board) that wraps, only [MustBeParallel](WordPairConstraint::MustBeParallel) and
+    /// [custom](CombinedConstraint::custom) constraints are allowed, the others would check the words at their unwrapped coordinates.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::generator::CrosswordGeneratorSettings;
+    /// # use crossword_generator::crossword::CrosswordSizeConstraint;
+    /// # use crossword_generator::config::SettingsError;
+    /// let mut settings = CrosswordGeneratorSettings::default();
+    /// settings.crossword_settings.size_constraints.push(CrosswordSizeConstraint::MaxLength(0));
+    ///
+    /// assert_eq!(settings.validate(), Err(SettingsError::UnsatisfiableConstraint(CrosswordSizeConstraint::MaxLength(0))));
+    /// ```
+    pub fn validate(&self) -> Result<(), SettingsError>
+    {
+        let constraints = &self.crossword_settings.size_constraints;
+        let max_length = constraints.iter().filter_map(|c| match *c { CrosswordSizeConstraint::MaxLength(l) | CrosswordSizeConstraint::ExactSize(l, _) => Some(l), _ => None }).min();
+        let max_height = constraints.iter().filter_map(|c| match *c { CrosswordSizeConstraint::MaxHeight(h) | CrosswordSizeConstraint::ExactSize(_, h) => Some(h), _ => None }).min();
+
+        for constraint in constraints.iter()
+        {
+            match *constraint
+            {
+                CrosswordSizeConstraint::MaxLength(0) |
+                CrosswordSizeConstraint::MaxHeight(0) |
+                CrosswordSizeConstraint::MaxArea(0) |
+                CrosswordSizeConstraint::MaxCells(0) |
+                CrosswordSizeConstraint::ExactSize(0, _) |
+                CrosswordSizeConstraint::ExactSize(_, 0) |
+                CrosswordSizeConstraint::MinAspectRatio(_, 0) |
+                CrosswordSizeConstraint::MaxAspectRatio(_, 0) |
+                CrosswordSizeConstraint::MaxEmptyCellRatio(_, 0) => return Err(SettingsError::UnsatisfiableConstraint(constraint.clone())),
+                CrosswordSizeConstraint::MinLength(l) | CrosswordSizeConstraint::ExactSize(l, _) if max_length.is_some_and(|m| l > m) => return Err(SettingsError::UnsatisfiableConstraint(constraint.clone())),
+                CrosswordSizeConstraint::MinHeight(h) | CrosswordSizeConstraint::ExactSize(_, h) if max_height.is_some_and(|m| h > m) => return Err(SettingsError::UnsatisfiableConstraint(constraint.clone())),
+                _ => {}
+            }
+        }
+
+        if self.board.as_ref().is_some_and(|board| board.wrap)
+        {
+            let size_constraint = constraints.first().map(|c| format!("{:?}", c));
+            let constraint = self.crossword_settings.constraints.iter().find(|c| !is_checked_on_torus(c)).map(|c| format!("{:?}", c));
+            let pair_constraint = self.crossword_settings.word_pair_constraints.iter().find(|c| !matches!(c, WordPairConstraint::MustBeParallel(..))).map(|c| format!("{:?}", c));
+            if let Some(constraint) = size_constraint.or(constraint).or(pair_constraint)
+            {
+                return Err(SettingsError::ConstraintOnTorus(constraint));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CrosswordGenerator
+{
+    /// [Validates](CrosswordGeneratorSettings::validate) the settings and checks that every word can be placed in a crossword that satisfies them
+    ///
+    /// Without this check, a bad combination of words and settings only shows as an empty result of the generation.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::generator::CrosswordGenerator;
+    /// # use crossword_generator::crossword::CrosswordSizeConstraint;
+    /// # use crossword_generator::config::SettingsError;
+    /// let mut generator = CrosswordGenerator::default();
+    /// generator.words = ["hello", "local"].into_iter().map(|s| s.to_owned()).collect();
+    /// generator.settings.crossword_settings.size_constraints = vec![CrosswordSizeConstraint::MaxLength(4), CrosswordSizeConstraint::MaxHeight(4)];
+    ///
+    /// assert!(matches!(generator.validate(), Err(SettingsError::WordDoesNotFit{ .. })));
+    /// ```
+    pub fn validate(&self) -> Result<(), SettingsError>
+    {
+        self.settings.validate()?;
+
+        for word in self.words.iter()
+        {
+            let length = word.chars().count();
+            if length == 0
+            {
+                return Err(SettingsError::EmptyWord);
+            }
+
+            let fits = |constraint: &CrosswordSizeConstraint| match *constraint
+            {
+                CrosswordSizeConstraint::MaxArea(a) | CrosswordSizeConstraint::MaxCells(a) => length <= a,
+                CrosswordSizeConstraint::ExactSize(l, h) => length <= l.max(h),
+                _ => true
+            };
+            if let Some(constraint) = self.settings.crossword_settings.size_constraints.iter().find(|c| !fits(c))
+            {
+                return Err(SettingsError::WordDoesNotFit{ word: word.clone(), constraint: constraint.clone() });
+            }
+
+            let max_length = self.settings.crossword_settings.size_constraints.iter().filter_map(|c| match *c { CrosswordSizeConstraint::MaxLength(l) | CrosswordSizeConstraint::ExactSize(l, _) => Some(l), _ => None }).min();
+            let max_height = self.settings.crossword_settings.size_constraints.iter().filter_map(|c| match *c { CrosswordSizeConstraint::MaxHeight(h) | CrosswordSizeConstraint::ExactSize(_, h) => Some(h), _ => None }).min();
+            if let (Some(max_length), Some(max_height)) = (max_length, max_height)
+            {
+                if length > max_length && length > max_height
+                {
+                    let constraint = CrosswordSizeConstraint::MaxLength(max_length);
+                    return Err(SettingsError::WordDoesNotFit{ word: word.clone(), constraint });
+                }
+            }
+
+            if self.settings.board.as_ref().is_some_and(|board| board.get_placements(word, &self.settings.directions).is_empty())
+            {
+                return Err(SettingsError::WordDoesNotFitBoard(word.clone()));
+            }
+        }
+
+        for constraint in self.settings.crossword_settings.word_pair_constraints.iter()
+        {
+            let (first, second) = constraint.get_words();
+            if let Some(word) = [first, second].into_iter().find(|w| !self.words.iter().any(|word| word == w))
+            {
+                return Err(SettingsError::UnknownPairWord(word.to_owned()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+    use crate::board::Board;
+    use crate::constraint::WordPairConstraint;
+
+    #[test]
+    fn test_presets() {
+        for name in PRESET_NAMES
+        {
+            let settings = CrosswordGeneratorSettings::preset(name).unwrap();
+            assert_eq!(settings.validate(), Ok(()));
+            assert_eq!(CrosswordGeneratorSettings::from_toml_str(&format!("preset = \"{}\"", name)), Ok(settings));
+        }
+
+        assert_eq!(CrosswordGeneratorSettings::preset("tabloid"), Err(SettingsError::UnknownPreset("tabloid".to_owned())));
+    }
+
+    #[test]
+    fn test_settings_from_str() {
+        assert_eq!(CrosswordGeneratorSettings::from_json_str("{}"), Ok(CrosswordGeneratorSettings::default()));
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str(""), Ok(CrosswordGeneratorSettings::default()));
+
+        let toml = CrosswordGeneratorSettings::from_toml_str("[word_compatibility_settings]\nside_by_side = true\n").unwrap();
+        assert_eq!(toml.word_compatibility_settings, WordCompatibilitySettings { side_by_side: true, ..Default::default() });
+
+        assert!(matches!(CrosswordGeneratorSettings::from_json_str("{\"preset\": 1}"), Err(SettingsError::Parse(_))));
+        assert!(matches!(CrosswordGeneratorSettings::from_json_str("{\"word_compatibility_settings\": {\"side_by_side\": 1}}"), Err(SettingsError::Parse(_))));
+        assert!(matches!(CrosswordGeneratorSettings::from_toml_str("preset = "), Err(SettingsError::Parse(_))));
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str("[word_compatibility_settings]\nside_by_sid = true"), Err(SettingsError::Parse("unknown key \"word_compatibility_settings.side_by_sid\"".to_owned())));
+        assert_eq!(CrosswordGeneratorSettings::from_json_str("{\"crossword_settings\": {\"size_constraints\": [], \"max_words\": 3}}"), Err(SettingsError::Parse("unknown key \"crossword_settings.max_words\"".to_owned())));
+        assert_eq!(CrosswordGeneratorSettings::from_json_str("{\"preset\": \"kids-loose\", \"seed\": 1}"), Err(SettingsError::Parse("unknown key \"seed\"".to_owned())));
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str("[board]\nwidth = 5\nheight = 4\nwrap = true"), Ok(CrosswordGeneratorSettings { board: Some(Board::new_torus(5, 4)), ..Default::default() }));
+        assert!(matches!(CrosswordGeneratorSettings::from_toml_str("[board]\nwidth = 5\nheight = 4\nwarp = true"), Err(SettingsError::Parse(_))));
+        let lenient: CrosswordGeneratorSettings = serde_json::from_str("{\"word_compatibility_settings\": {\"side_by_side\": false, \"head_by_head\": false, \"side_by_head\": false, \"corner_by_corner\": true, \"extra\": 1}, \"crossword_settings\": {\"size_constraints\": []}}").unwrap();
+        assert_eq!(lenient, CrosswordGeneratorSettings::default());
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str("[crossword_settings]\nsize_constraints = [{ MaxHeight = 0 }]"), Err(SettingsError::UnsatisfiableConstraint(CrosswordSizeConstraint::MaxHeight(0))));
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str("[crossword_settings]\nsize_constraints = [{ MinLength = 8 }, { MaxLength = 7 }]"), Err(SettingsError::UnsatisfiableConstraint(CrosswordSizeConstraint::MinLength(8))));
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str("[crossword_settings]\nsize_constraints = [{ ExactSize = [5, 6] }, { MaxHeight = 5 }]"), Err(SettingsError::UnsatisfiableConstraint(CrosswordSizeConstraint::ExactSize(5, 6))));
+        assert_eq!(CrosswordGeneratorSettings::from_toml_str("[crossword_settings]\nsize_constraints = [{ MaxAspectRatio = [1, 0] }]"), Err(SettingsError::UnsatisfiableConstraint(CrosswordSizeConstraint::MaxAspectRatio(1, 0))));
+    }
+
+    #[test]
+    fn test_generator_validate() {
+        let mut generator = CrosswordGenerator::default();
+        generator.words = ["hello", "local"].into_iter().map(|s| s.to_owned()).collect();
+        assert_eq!(generator.validate(), Ok(()));
+
+        generator.settings.crossword_settings.size_constraints = vec![CrosswordSizeConstraint::MaxLength(4)];
+        assert_eq!(generator.validate(), Ok(()));
+
+        generator.settings.crossword_settings.size_constraints = vec![CrosswordSizeConstraint::MaxArea(4)];
+        assert_eq!(generator.validate(), Err(SettingsError::WordDoesNotFit{ word: "hello".to_owned(), constraint: CrosswordSizeConstraint::MaxArea(4) }));
+
+        generator.settings.crossword_settings.size_constraints = vec![CrosswordSizeConstraint::ExactSize(3, 4)];
+        assert_eq!(generator.validate(), Err(SettingsError::WordDoesNotFit{ word: "hello".to_owned(), constraint: CrosswordSizeConstraint::ExactSize(3, 4) }));
+
+        generator.settings.crossword_settings.size_constraints = vec![];
+        generator.settings.board = Some(Board::from_ascii("....\n.#..\n...."));
+        assert_eq!(generator.validate(), Err(SettingsError::WordDoesNotFitBoard("hello".to_owned())));
+
+        generator.settings.board = None;
+        generator.settings.crossword_settings.word_pair_constraints = vec![WordPairConstraint::MustCross("hello".to_owned(), "world".to_owned())];
+        assert_eq!(generator.validate(), Err(SettingsError::UnknownPairWord("world".to_owned())));
+
+        generator.words.push(String::new());
+        assert_eq!(generator.validate(), Err(SettingsError::EmptyWord));
+    }
+
+    #[test]
+    fn test_torus_constraints() {
+        let mut settings = CrosswordGeneratorSettings { board: Some(Board::new_torus(6, 4)), ..Default::default() };
+        settings.crossword_settings.word_pair_constraints = vec![WordPairConstraint::MustBeParallel("hello".to_owned(), "world".to_owned())];
+        settings.crossword_settings.constraints = vec![CombinedConstraint::Not(Box::new(CombinedConstraint::custom(CrosswordSizeConstraint::MaxLength(3))))];
+        assert_eq!(settings.validate(), Ok(()));
+
+        settings.crossword_settings.constraints.push(CombinedConstraint::Crossing(CrossingConstraint::MinCrossingsPerWord(1)));
+        let error = settings.validate().unwrap_err();
+        assert_eq!(error, SettingsError::ConstraintOnTorus("Crossing(MinCrossingsPerWord(1))".to_owned()));
+        assert_eq!(error.to_string(), "the constraint Crossing(MinCrossingsPerWord(1)) can't be used on a torus board, it doesn't see the words wrapped around the edges");
+
+        settings.crossword_settings.constraints.clear();
+        settings.crossword_settings.size_constraints = vec![CrosswordSizeConstraint::MaxLength(6)];
+        assert_eq!(settings.validate(), Err(SettingsError::ConstraintOnTorus("MaxLength(6)".to_owned())));
+
+        settings.board = Some(Board::new(6, 4));
+        assert_eq!(settings.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,616 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+
+
+/// A rule that a [crossword](Crossword) has to follow, checked during [generation](crate::generator::CrosswordGenerator)
+///
+/// Implement it to add your own rules with [CombinedConstraint::custom].
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+/// # use crossword_generator::crossword::Crossword;
+/// # use crossword_generator::constraint::CrosswordConstraint;
+/// struct MaxWords(usize);
+///
+/// impl CrosswordConstraint for MaxWords
+/// {
+///     fn is_crossword_valid(&self, cw: &Crossword) -> bool
+///     {
+///         cw.get_words().len() <= self.0
+///     }
+///
+///     // a crossword with too many words stays invalid whatever is added to it
+///     fn can_be_extended(&self, cw: &Crossword) -> bool
+///     {
+///         self.is_crossword_valid(cw)
+///     }
+/// }
+///
+/// let cw = Crossword::new(&[                                                                                 //     -----
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cow", id: 0},      //    |o    |
+/// ]);                                                                                                        //    |w    |
+///                                                                                                            //     -----
+/// assert!(!MaxWords(1).can_be_extended(&cw));
+/// ```
+pub trait CrosswordConstraint: Send + Sync
+{
+    /// Checks if the finished [crossword](Crossword) follows the rule
+    fn is_crossword_valid(&self, cw: &Crossword) -> bool;
+
+    /// Checks if a [crossword](Crossword) that contains this one can still follow the rule, crosswords that can't are dropped during generation
+    ///
+    /// The default implementation never drops anything, which is always correct but doesn't make the generation faster.
+    fn can_be_extended(&self, _cw: &Crossword) -> bool
+    {
+        true
+    }
+
+    /// Checks if the [crossword](Crossword) can still follow the rule after the remaining words are added to it (a word with several copies left is listed once per copy), crosswords that can't are dropped during generation
+    ///
+    /// The default implementation calls [can_be_extended](CrosswordConstraint::can_be_extended), override it for rules that depend on the words that are not placed yet.
+    fn can_be_extended_with(&self, cw: &Crossword, _remaining_words: &[&str]) -> bool
+    {
+        self.can_be_extended(cw)
+    }
+}
+
+impl CrosswordConstraint for CrosswordSizeConstraint
+{
+    fn is_crossword_valid(&self, cw: &Crossword) -> bool
+    {
+        CrosswordSizeConstraint::is_crossword_valid(self, cw)
+    }
+
+    fn can_be_extended(&self, cw: &Crossword) -> bool
+    {
+        CrosswordSizeConstraint::can_be_extended(self, cw)
+    }
+}
+
+/// A shared user-defined [constraint](CrosswordConstraint), compared by [address](ArcByAddress)
+pub type CustomConstraint = ArcByAddress<dyn CrosswordConstraint>;
+
+/// Represents a silhouette of allowed cells, a [crossword](Crossword) satisfies it if all its letters fall inside the mask after some translation
+///
+/// The translation is searched because [normalized](Crossword::normalize) crosswords always start at the origin.
+/// Adding words never helps a crossword to fit, so the mask is also used to drop partial crosswords during generation.
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+/// # use crossword_generator::crossword::Crossword;
+/// # use crossword_generator::constraint::{ShapeMask, CrosswordConstraint};
+/// let mask = ShapeMask::from_ascii("\
+/// .##.
+/// ####
+/// .##.");
+///
+/// let cw = Crossword::new(&[                                                                                 //     ---
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cat", id: 0},      //    |c|
+/// ]);                                                                                                        //    |a|
+///                                                                                                            //    |t|
+///                                                                                                            //     ---
+/// assert_eq!(mask.get_offset(&cw), Some((1, 0)));
+/// assert!(mask.is_crossword_valid(&cw));
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct ShapeMask
+{
+    /// Rows of the mask, true for allowed cells
+    pub cells: Vec<Vec<bool>>
+}
+
+impl ShapeMask
+{
+    /// Reads a mask from ASCII art, one line per row, ' ' and '.' are outside of the mask and any other character is inside
+    ///
+    /// Rows can have different lengths, missing cells are outside of the mask. Leading and trailing empty lines are ignored.
+    pub fn from_ascii(mask: &str) -> ShapeMask
+    {
+        let lines: Vec<&str> = mask.lines().collect();
+        let first = lines.iter().position(|l| !l.trim().is_empty()).unwrap_or(lines.len());
+        let last = lines.iter().rposition(|l| !l.trim().is_empty()).map_or(first, |l| l + 1);
+
+        let mut cells: Vec<Vec<bool>> = lines[first..last].iter()
+            .map(|line| line.chars().map(|ch| ch != ' ' && ch != '.').collect())
+            .collect();
+        let width = cells.iter().map(|row| row.len()).max().unwrap_or(0);
+        cells.iter_mut().for_each(|row| row.resize(width, false));
+
+        ShapeMask { cells }
+    }
+
+    fn is_allowed(&self, x: usize, y: usize) -> bool
+    {
+        self.cells.get(y).and_then(|row| row.get(x)).copied().unwrap_or(false)
+    }
+
+    /// Returns the smallest translation (x, y), in reading order, that puts all letters of the [crossword](Crossword) inside the mask, or None if there is none
+    pub fn get_offset(&self, cw: &Crossword) -> Option<(usize, usize)>
+    {
+        let (width, height) = cw.get_size();
+        let mask_height = self.cells.len();
+        let mask_width = self.cells.iter().map(|row| row.len()).max().unwrap_or(0);
+        if width > mask_width || height > mask_height { return None; }
+
+        let letters: Vec<(usize, usize)> = cw.generate_char_table().iter().enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().filter(|(_, ch)| **ch != ' ').map(move |(x, _)| (x, y)))
+            .collect();
+
+        (0..=mask_height - height)
+            .flat_map(|dy| (0..=mask_width - width).map(move |dx| (dx, dy)))
+            .find(|(dx, dy)| letters.iter().all(|(x, y)| self.is_allowed(x + dx, y + dy)))
+    }
+}
+
+impl CrosswordConstraint for ShapeMask
+{
+    fn is_crossword_valid(&self, cw: &Crossword) -> bool
+    {
+        self.get_offset(cw).is_some()
+    }
+
+    fn can_be_extended(&self, cw: &Crossword) -> bool
+    {
+        self.is_crossword_valid(cw)
+    }
+}
+
+/// Represents a cell that has to contain one of the given letters, for example a circled cell of a hidden message
+///
+/// The position is in the coordinates of the [crossword](Crossword), so it is most useful for [absolute](Crossword::new_absolute) crosswords generated on a [board](crate::board::Board).
+/// Partial crosswords are dropped during generation only if they are absolute, the words of relative ones still move when words are added.
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+/// # use crossword_generator::crossword::Crossword;
+/// # use crossword_generator::constraint::{CellLetterConstraint, CrosswordConstraint};
+/// let cw = Crossword::new_absolute(&[                                                                        //     -----
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+/// ]);                                                                                                        //     -----
+///
+/// assert!(CellLetterConstraint::new(WordPosition { x: 1, y: 0 }, 'a').is_crossword_valid(&cw));
+/// assert!(!CellLetterConstraint::new(WordPosition { x: 1, y: 0 }, 'o').can_be_extended(&cw));
+/// assert!(CellLetterConstraint::with_letters(WordPosition { x: 2, y: 0 }, "st").is_crossword_valid(&cw));
+/// assert!(!CellLetterConstraint::new(WordPosition { x: 2, y: 1 }, 's').is_crossword_valid(&cw));
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct CellLetterConstraint
+{
+    pub position: WordPosition,
+    /// Allowed letters, compared exactly (the letters of the words are not lowercased)
+    pub letters: BTreeSet<char>
+}
+
+impl CellLetterConstraint
+{
+    /// Creates a constraint that requires the letter in the cell
+    pub fn new(position: WordPosition, letter: char) -> CellLetterConstraint
+    {
+        CellLetterConstraint { position, letters: BTreeSet::from([letter]) }
+    }
+
+    /// Creates a constraint that requires one of the letters of the string in the cell
+    pub fn with_letters(position: WordPosition, letters: &str) -> CellLetterConstraint
+    {
+        CellLetterConstraint { position, letters: letters.chars().collect() }
+    }
+}
+
+impl CrosswordConstraint for CellLetterConstraint
+{
+    fn is_crossword_valid(&self, cw: &Crossword) -> bool
+    {
+        cw.get_letter(&self.position).is_some_and(|ch| self.letters.contains(&ch))
+    }
+
+    fn can_be_extended(&self, cw: &Crossword) -> bool
+    {
+        !cw.is_absolute() || cw.get_letter(&self.position).is_none_or(|ch| self.letters.contains(&ch))
+    }
+}
+
+/// Represents a rule about the crossings of the [words](Word) of a [crossword](Crossword), so that no word hangs off a single letter
+///
+/// Cells shared by two words are called checked cells. They are counted from [Word::get_intersection_indices].
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+/// # use crossword_generator::crossword::Crossword;
+/// # use crossword_generator::constraint::{CrossingConstraint, CrosswordConstraint};
+/// let cw = Crossword::new(&[                                                                                 //     -----
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cow", id: 0},      //    |o    |
+/// ]);                                                                                                        //    |w    |
+///                                                                                                            //     -----
+/// assert!(CrossingConstraint::MinCrossingsPerWord(1).is_crossword_valid(&cw));
+/// assert!(!CrossingConstraint::MinCrossingsPerWord(2).is_crossword_valid(&cw));
+/// assert!(CrossingConstraint::MinCrossingsPerWord(2).can_be_extended_with(&cw, &["two"]));
+/// assert!(!CrossingConstraint::MinCheckedCellRatio(1, 4).is_crossword_valid(&cw));
+/// assert!(CrossingConstraint::MinCheckedCellRatio(1, 5).is_crossword_valid(&cw));
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum CrossingConstraint
+{
+    /// Every word crosses at least this many other words
+    MinCrossingsPerWord(usize),
+    /// At least numerator / denominator of the cells with letters are checked
+    MinCheckedCellRatio(usize, usize)
+}
+
+impl CrosswordConstraint for CrossingConstraint
+{
+    fn is_crossword_valid(&self, cw: &Crossword) -> bool
+    {
+        self.can_be_extended_with(cw, &[])
+    }
+
+    /// A word gets at most one crossing from every remaining word, and every remaining word adds at most as many checked cells as it has letters while the number of cells never decreases
+    fn can_be_extended_with(&self, cw: &Crossword, remaining_words: &[&str]) -> bool
+    {
+        match *self
+        {
+            CrossingConstraint::MinCrossingsPerWord(n) => cw.get_words().iter().all(|w| cw.get_crossing_count(w) + remaining_words.len() >= n),
+            CrossingConstraint::MinCheckedCellRatio(n, d) =>
+            {
+                let max_checked = cw.get_checked_cell_count() + remaining_words.iter().map(|w| w.chars().count()).sum::<usize>();
+                max_checked * d >= n * cw.get_cell_count()
+            }
+        }
+    }
+}
+
+/// Represents a rule about two [words](Word) of a [crossword](Crossword), for example two theme entries
+///
+/// The rule is checked when the second of the words is placed, see [CrosswordSettings::is_step_allowed]. If one of the words is not in the crossword, the rule is satisfied, if a word has several copies, the rule applies to each of them.
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+/// # use crossword_generator::crossword::Crossword;
+/// # use crossword_generator::constraint::WordPairConstraint;
+/// let cw = Crossword::new(&[                                                                                 //     -----
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cow", id: 0},      //    |o    |
+/// ]);                                                                                                        //    |w    |
+///                                                                                                            //     -----
+/// assert!(WordPairConstraint::MustCross("cat".to_owned(), "cow".to_owned()).is_crossword_valid(&cw));
+/// assert!(!WordPairConstraint::MustBeParallel("cat".to_owned(), "cow".to_owned()).is_crossword_valid(&cw));
+/// assert!(WordPairConstraint::MustNotCross("cat".to_owned(), "dog".to_owned()).is_crossword_valid(&cw));
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum WordPairConstraint
+{
+    /// The words cross each other
+    MustCross(String, String),
+    /// The words neither cross nor touch each other, not even with corners
+    MustNotCross(String, String),
+    /// The words lie on [parallel](WordDirection::is_parallel_to) lines
+    MustBeParallel(String, String),
+    /// The [distance](Word::get_distance) of the words is at most the given number of cells
+    MaxDistance(String, String, usize)
+}
+
+impl WordPairConstraint
+{
+    /// Returns the two words of the rule
+    pub fn get_words(&self) -> (&str, &str)
+    {
+        match self
+        {
+            WordPairConstraint::MustCross(first, second) |
+            WordPairConstraint::MustNotCross(first, second) |
+            WordPairConstraint::MustBeParallel(first, second) |
+            WordPairConstraint::MaxDistance(first, second, _) => (first, second)
+        }
+    }
+
+    /// Checks if the two placed [words](Word) follow the rule, the order of the words does not matter
+    pub fn are_words_valid(&self, first: &Word, second: &Word) -> bool
+    {
+        match *self
+        {
+            WordPairConstraint::MustCross(_, _) => first.get_intersection_indices(second).is_some(),
+            WordPairConstraint::MustNotCross(_, _) => first.get_distance(second) > 1,
+            WordPairConstraint::MustBeParallel(_, _) => first.direction.is_parallel_to(&second.direction),
+            WordPairConstraint::MaxDistance(_, _, distance) => first.get_distance(second) <= distance
+        }
+    }
+
+    /// Checks if the [crossword](Crossword) follows the rule
+    pub fn is_crossword_valid(&self, cw: &Crossword) -> bool
+    {
+        let (first, second) = self.get_words();
+        cw.find_words(first).all(|first| cw.find_words(second).filter(|second| *second != first).all(|second| self.are_words_valid(first, second)))
+    }
+
+    /// Checks if the [word](Word) can be added to the [crossword](Crossword) without breaking the rule
+    pub fn is_step_allowed(&self, cw: &Crossword, step: &Word) -> bool
+    {
+        let (first, second) = self.get_words();
+        let other = if step.value == first { second } else if step.value == second { first } else { return true; };
+
+        cw.find_words(other).all(|other| self.are_words_valid(step, other))
+    }
+}
+
+/// Represents a boolean combination of [constraints](CrosswordConstraint)
+///
+/// All variants except [Custom](CombinedConstraint::Custom) can be serialized, so they can be written in settings files:
+///
+/// ```text
+/// [[crossword_settings.constraints]]
+/// Or = [{ Size = { MaxLength = 10 } }, { Size = { MaxHeight = 10 } }]
+/// ```
+///
+/// When pruning partial crosswords, [Not](CombinedConstraint::Not) never drops anything, because a crossword that breaks a rule can start following it after more words are added.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum CombinedConstraint
+{
+    Size(CrosswordSizeConstraint),
+    /// All of the constraints are satisfied
+    And(Vec<CombinedConstraint>),
+    /// At least one of the constraints is satisfied
+    Or(Vec<CombinedConstraint>),
+    /// The constraint is not satisfied
+    Not(Box<CombinedConstraint>),
+    /// All letters fit inside the [mask](ShapeMask)
+    Mask(ShapeMask),
+    /// The cell contains one of the letters
+    CellLetter(CellLetterConstraint),
+    /// The words cross each other enough
+    Crossing(CrossingConstraint),
+    /// A user-defined constraint, created with [custom](CombinedConstraint::custom)
+    #[serde(skip)]
+    Custom(CustomConstraint)
+}
+
+impl CombinedConstraint
+{
+    /// Wraps a user-defined [constraint](CrosswordConstraint)
+    pub fn custom(constraint: impl CrosswordConstraint + 'static) -> CombinedConstraint
+    {
+        CombinedConstraint::Custom(ArcByAddress(Arc::new(constraint)))
+    }
+}
+
+impl CrosswordConstraint for CombinedConstraint
+{
+    fn is_crossword_valid(&self, cw: &Crossword) -> bool
+    {
+        match self
+        {
+            CombinedConstraint::Size(c) => c.is_crossword_valid(cw),
+            CombinedConstraint::And(cs) => cs.iter().all(|c| c.is_crossword_valid(cw)),
+            CombinedConstraint::Or(cs) => cs.iter().any(|c| c.is_crossword_valid(cw)),
+            CombinedConstraint::Not(c) => !c.is_crossword_valid(cw),
+            CombinedConstraint::Mask(m) => m.is_crossword_valid(cw),
+            CombinedConstraint::CellLetter(c) => c.is_crossword_valid(cw),
+            CombinedConstraint::Crossing(c) => c.is_crossword_valid(cw),
+            CombinedConstraint::Custom(c) => c.0.is_crossword_valid(cw)
+        }
+    }
+
+    fn can_be_extended(&self, cw: &Crossword) -> bool
+    {
+        match self
+        {
+            CombinedConstraint::Size(c) => c.can_be_extended(cw),
+            CombinedConstraint::And(cs) => cs.iter().all(|c| c.can_be_extended(cw)),
+            CombinedConstraint::Or(cs) => cs.iter().any(|c| c.can_be_extended(cw)),
+            CombinedConstraint::Not(_) => true,
+            CombinedConstraint::Mask(m) => m.can_be_extended(cw),
+            CombinedConstraint::CellLetter(c) => c.can_be_extended(cw),
+            CombinedConstraint::Crossing(c) => c.can_be_extended(cw),
+            CombinedConstraint::Custom(c) => c.0.can_be_extended(cw)
+        }
+    }
+
+    fn can_be_extended_with(&self, cw: &Crossword, remaining_words: &[&str]) -> bool
+    {
+        match self
+        {
+            CombinedConstraint::Size(c) => c.can_be_extended(cw),
+            CombinedConstraint::And(cs) => cs.iter().all(|c| c.can_be_extended_with(cw, remaining_words)),
+            CombinedConstraint::Or(cs) => cs.iter().any(|c| c.can_be_extended_with(cw, remaining_words)),
+            CombinedConstraint::Not(_) => true,
+            CombinedConstraint::Mask(m) => m.can_be_extended(cw),
+            CombinedConstraint::CellLetter(c) => c.can_be_extended(cw),
+            CombinedConstraint::Crossing(c) => c.can_be_extended_with(cw, remaining_words),
+            CombinedConstraint::Custom(c) => c.0.can_be_extended_with(cw, remaining_words)
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    struct MaxWords(usize);
+
+    impl CrosswordConstraint for MaxWords
+    {
+        fn is_crossword_valid(&self, cw: &Crossword) -> bool
+        {
+            cw.get_words().len() <= self.0
+        }
+    }
+
+    #[test]
+    fn test_combined_constraint() {
+        let cw = Crossword::new(
+            &[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+            ]);
+
+        let small = CombinedConstraint::Size(CrosswordSizeConstraint::MaxArea(20));
+        let wide = CombinedConstraint::Size(CrosswordSizeConstraint::MinLength(5));
+
+        assert!(!small.is_crossword_valid(&cw));
+        assert!(!small.can_be_extended(&cw));
+        assert!(wide.is_crossword_valid(&cw));
+
+        let or = CombinedConstraint::Or(vec![small.clone(), wide.clone()]);
+        assert!(or.is_crossword_valid(&cw));
+        assert!(or.can_be_extended(&cw));
+
+        let and = CombinedConstraint::And(vec![small.clone(), wide.clone()]);
+        assert!(!and.is_crossword_valid(&cw));
+        assert!(!and.can_be_extended(&cw));
+
+        let not = CombinedConstraint::Not(Box::new(small.clone()));
+        assert!(not.is_crossword_valid(&cw));
+        assert!(CombinedConstraint::Not(Box::new(wide)).can_be_extended(&cw));
+
+        assert!(CombinedConstraint::custom(MaxWords(2)).is_crossword_valid(&cw));
+        assert!(!CombinedConstraint::custom(MaxWords(1)).is_crossword_valid(&cw));
+        assert!(CombinedConstraint::custom(MaxWords(1)).can_be_extended(&cw));
+    }
+
+    #[test]
+    fn test_shape_mask() {
+        let mask = ShapeMask::from_ascii("\n ## ##\n#######\n #####\n  ###\n   #\n\n");
+        assert_eq!(mask.cells.len(), 5);
+        assert!(mask.cells.iter().all(|row| row.len() == 7));
+        assert_eq!(mask.cells[0], vec![false, true, true, false, true, true, false]);
+
+        let hello = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0};
+        let cw = Crossword::new(std::slice::from_ref(&hello));
+        assert_eq!(mask.get_offset(&cw), Some((0, 1)));
+
+        let cw = Crossword::new(&[hello.clone(), Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "hi", id: 0}]);
+        assert_eq!(mask.get_offset(&cw), Some((1, 1)));
+
+        let cw = Crossword::new(&[hello.clone(), Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "hymn", id: 0}]);
+        assert_eq!(mask.get_offset(&cw), None);
+        assert!(!CombinedConstraint::Mask(mask.clone()).can_be_extended(&cw));
+
+        let cw = Crossword::new(&[Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "sesame", id: 0}, Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "sun", id: 0}]);
+        assert_eq!(mask.get_offset(&cw), None);
+
+        assert_eq!(ShapeMask::from_ascii("").get_offset(&Crossword::new(&[hello])), None);
+    }
+
+    #[test]
+    fn test_cell_letter_constraint() {
+        let words = [
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+        ];
+        let circled = CellLetterConstraint::with_letters(WordPosition { x: 2, y: 3 }, "ae");
+
+        let absolute = Crossword::new_absolute(&words);
+        assert!(circled.is_crossword_valid(&absolute));
+        assert!(!CellLetterConstraint::new(WordPosition { x: 2, y: 4 }, 'x').can_be_extended(&absolute));
+        assert!(CellLetterConstraint::new(WordPosition { x: 3, y: 4 }, 'x').can_be_extended(&absolute));
+        assert!(!CellLetterConstraint::new(WordPosition { x: 3, y: 4 }, 'x').is_crossword_valid(&absolute));
+
+        let relative = Crossword::new(&words[..1]);
+        assert!(!CellLetterConstraint::new(WordPosition { x: 0, y: 0 }, 'x').is_crossword_valid(&relative));
+        assert!(CellLetterConstraint::new(WordPosition { x: 0, y: 0 }, 'x').can_be_extended(&relative));
+
+        let settings = CrosswordSettings {
+            constraints: vec![
+                CombinedConstraint::CellLetter(circled.clone()),
+                CombinedConstraint::And(vec![CombinedConstraint::CellLetter(CellLetterConstraint::new(WordPosition { x: 7, y: 0 }, 'o'))]),
+                CombinedConstraint::Or(vec![CombinedConstraint::CellLetter(CellLetterConstraint::new(WordPosition { x: 0, y: 0 }, 'h'))]),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(absolute.get_constrained_letters(&settings), vec![(WordPosition { x: 2, y: 3 }, Some('a')), (WordPosition { x: 7, y: 0 }, None)]);
+        assert!(!settings.is_crossword_valid(&absolute));
+    }
+
+    #[test]
+    fn test_crossing_constraint() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+            Word{position: WordPosition { x: 0, y: 3 }, direction: WordDirection::Right, value: "tea", id: 0},
+        ]);
+
+        let two = CrossingConstraint::MinCrossingsPerWord(2);
+        assert!(!two.is_crossword_valid(&cw));
+        assert!(two.can_be_extended_with(&cw, &["hat"]));
+        assert!(!two.can_be_extended_with(&cw, &[]));
+        assert!(!CrossingConstraint::MinCrossingsPerWord(3).can_be_extended_with(&cw, &["hat"]));
+        assert!(CrossingConstraint::MinCrossingsPerWord(1).is_crossword_valid(&cw));
+
+        let half = CrossingConstraint::MinCheckedCellRatio(1, 2);
+        assert!(!half.is_crossword_valid(&cw));
+        assert!(!half.can_be_extended_with(&cw, &["ab"]));
+        assert!(half.can_be_extended_with(&cw, &["abcd"]));
+        assert!(CrossingConstraint::MinCheckedCellRatio(2, 11).is_crossword_valid(&cw));
+
+        let combined = CombinedConstraint::And(vec![CombinedConstraint::Crossing(two), CombinedConstraint::custom(MaxWords(4))]);
+        assert!(combined.can_be_extended_with(&cw, &["hat"]));
+        assert!(combined.can_be_extended(&cw));
+        assert!(!combined.is_crossword_valid(&cw));
+    }
+
+    #[test]
+    fn test_word_pair_constraint() {
+        let hello = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0};
+        let cw = Crossword::new(&[
+            hello.clone(),
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+        ]);
+        let tea = Word{position: WordPosition { x: 0, y: 3 }, direction: WordDirection::Right, value: "tea", id: 0};
+        let tie = Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Right, value: "tie", id: 0};
+
+        let must_cross = WordPairConstraint::MustCross("tea".to_owned(), "local".to_owned());
+        assert!(must_cross.is_step_allowed(&cw, &tea));
+        assert!(!WordPairConstraint::MustCross("hello".to_owned(), "tea".to_owned()).is_step_allowed(&cw, &tea));
+        assert!(must_cross.is_step_allowed(&cw, &hello));
+        assert!(must_cross.is_crossword_valid(&cw));
+
+        let not_cross = WordPairConstraint::MustNotCross("tie".to_owned(), "local".to_owned());
+        assert!(!not_cross.is_step_allowed(&cw, &tie));
+        assert!(WordPairConstraint::MustNotCross("hello".to_owned(), "tie".to_owned()).is_step_allowed(&cw, &tie));
+
+        assert!(WordPairConstraint::MustBeParallel("tie".to_owned(), "hello".to_owned()).is_step_allowed(&cw, &tie));
+        assert!(!WordPairConstraint::MustBeParallel("tie".to_owned(), "local".to_owned()).is_step_allowed(&cw, &tie));
+
+        assert!(WordPairConstraint::MaxDistance("hello".to_owned(), "tie".to_owned(), 2).is_step_allowed(&cw, &tie));
+        assert!(!WordPairConstraint::MaxDistance("hello".to_owned(), "tie".to_owned(), 1).is_step_allowed(&cw, &tie));
+    }
+
+    #[test]
+    fn test_custom_constraint_eq() {
+        let custom = CombinedConstraint::custom(MaxWords(2));
+        assert_eq!(custom, custom.clone());
+        assert_ne!(custom, CombinedConstraint::custom(MaxWords(2)));
+        assert!(serde_json::to_string(&custom).is_err());
+    }
+
+    #[test]
+    fn test_combined_constraint_serde() {
+        let constraint = CombinedConstraint::Or(vec![
+            CombinedConstraint::Size(CrosswordSizeConstraint::MaxLength(10)),
+            CombinedConstraint::Not(Box::new(CombinedConstraint::Size(CrosswordSizeConstraint::ExactSize(3, 4)))),
+        ]);
+
+        let json = serde_json::to_string(&constraint).unwrap();
+        assert_eq!(json, r#"{"Or":[{"Size":{"MaxLength":10}},{"Not":{"Size":{"ExactSize":[3,4]}}}]}"#);
+        assert_eq!(serde_json::from_str::<CombinedConstraint>(&json).unwrap(), constraint);
+    }
+}
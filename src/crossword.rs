@@ -1,8 +1,9 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Serialize, Deserialize};
 
 use super::word::*;
+use super::constraint::*;
 
 
 /// Represents a size constraint on a [crossword](Crossword)
@@ -21,12 +22,28 @@ use super::word::*;
 /// //              v |      y        |
 /// //                 ---------------
 /// ```
+///
+/// Ratios are given as (numerator, denominator) pairs, so the constraints can be compared and serialized exactly.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 pub enum CrosswordSizeConstraint
 {
     MaxLength(usize),
     MaxHeight(usize),
     MaxArea(usize),
+    /// The length (width) is at least the value
+    MinLength(usize),
+    /// The height is at least the value
+    MinHeight(usize),
+    /// The size is exactly (length, height)
+    ExactSize(usize, usize),
+    /// length / height is at least numerator / denominator
+    MinAspectRatio(usize, usize),
+    /// length / height is at most numerator / denominator
+    MaxAspectRatio(usize, usize),
+    /// The share of cells without letters in the area is at most numerator / denominator
+    MaxEmptyCellRatio(usize, usize),
+    /// The number of cells with letters is at most the value
+    MaxCells(usize),
     #[default]
     None
 }
@@ -42,16 +59,72 @@ impl CrosswordSizeConstraint
             CrosswordSizeConstraint::MaxLength(l) => size.0 <= l,
             CrosswordSizeConstraint::MaxHeight(h) => size.1 <= h,
             CrosswordSizeConstraint::MaxArea(a) => size.0 * size.1 <= a,
+            CrosswordSizeConstraint::MinLength(l) => size.0 >= l,
+            CrosswordSizeConstraint::MinHeight(h) => size.1 >= h,
+            CrosswordSizeConstraint::ExactSize(l, h) => size == (l, h),
+            CrosswordSizeConstraint::MinAspectRatio(n, d) => size.0 * d >= n * size.1,
+            CrosswordSizeConstraint::MaxAspectRatio(n, d) => size.0 * d <= n * size.1,
+            CrosswordSizeConstraint::MaxEmptyCellRatio(n, d) => (size.0 * size.1 - cw.get_cell_count()) * d <= n * size.0 * size.1,
+            CrosswordSizeConstraint::MaxCells(c) => cw.get_cell_count() <= c,
+            CrosswordSizeConstraint::None => true
+        }
+    }
+
+    /// Checks if a [crossword](Crossword) that contains this one can still satisfy the [constraint](CrosswordSizeConstraint)
+    ///
+    /// Adding words never makes a crossword smaller, so a crossword that is already too big can be dropped during generation.
+    /// Constraints that a bigger crossword can satisfy again (minimums, ratios) always return true.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::{Crossword, CrosswordSizeConstraint};
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    /// ]);                                                                                                        //     -----
+    ///
+    /// assert!(!CrosswordSizeConstraint::ExactSize(3, 3).is_crossword_valid(&cw));
+    /// assert!(CrosswordSizeConstraint::ExactSize(3, 3).can_be_extended(&cw));
+    /// assert!(!CrosswordSizeConstraint::ExactSize(2, 3).can_be_extended(&cw));
+    /// ```
+    pub fn can_be_extended(&self, cw: &Crossword) -> bool
+    {
+        let size = cw.get_size();
+        match *self
+        {
+            CrosswordSizeConstraint::MaxLength(_) |
+            CrosswordSizeConstraint::MaxHeight(_) |
+            CrosswordSizeConstraint::MaxArea(_) |
+            CrosswordSizeConstraint::MaxCells(_) => self.is_crossword_valid(cw),
+            CrosswordSizeConstraint::ExactSize(l, h) => size.0 <= l && size.1 <= h,
+            CrosswordSizeConstraint::MinLength(_) |
+            CrosswordSizeConstraint::MinHeight(_) |
+            CrosswordSizeConstraint::MinAspectRatio(_, _) |
+            CrosswordSizeConstraint::MaxAspectRatio(_, _) |
+            CrosswordSizeConstraint::MaxEmptyCellRatio(_, _) |
             CrosswordSizeConstraint::None => true
         }
     }
 }
 
 /// Represents all settigns for a [crossword](Crossword)
+///
+/// ## Fields
+///
+/// size_constraints -> [size constraints](CrosswordSizeConstraint) that all have to be satisfied
+///
+/// constraints -> [combined constraints](CombinedConstraint) that all have to be satisfied, including user-defined ones
+///
+/// word_pair_constraints -> [rules for pairs of words](WordPairConstraint) that all have to be satisfied, they are checked when words are placed
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 pub struct CrosswordSettings
 {
-    pub size_constraints: Vec<CrosswordSizeConstraint>
+    pub size_constraints: Vec<CrosswordSizeConstraint>,
+    #[serde(default)]
+    pub constraints: Vec<CombinedConstraint>,
+    #[serde(default)]
+    pub word_pair_constraints: Vec<WordPairConstraint>
 }
 
 impl CrosswordSettings
@@ -59,18 +132,196 @@ impl CrosswordSettings
     /// Checks if the [crossword](Crossword) satisfies the [constraints](CrosswordSizeConstraint)
     pub fn is_crossword_valid(&self, cw: &Crossword) -> bool
     {
-        return self.size_constraints.iter().all(|c| c.is_crossword_valid(cw))
+        self.size_constraints.iter().all(|c| c.is_crossword_valid(cw)) && self.constraints.iter().all(|c| c.is_crossword_valid(cw)) &&
+            self.word_pair_constraints.iter().all(|c| c.is_crossword_valid(cw))
+    }
+
+    /// Checks if the [word](Word) can be added to the [crossword](Crossword) without breaking the [word pair constraints](WordPairConstraint)
+    pub fn is_step_allowed(&self, cw: &Crossword, step: &Word) -> bool
+    {
+        self.word_pair_constraints.iter().all(|c| c.is_step_allowed(cw, step))
+    }
+
+    /// Checks if a [crossword](Crossword) that contains this one can still satisfy the [constraints](CrosswordSizeConstraint), see [CrosswordConstraint::can_be_extended]
+    pub fn can_be_extended(&self, cw: &Crossword) -> bool
+    {
+        self.size_constraints.iter().all(|c| c.can_be_extended(cw)) && self.constraints.iter().all(|c| c.can_be_extended(cw))
+    }
+
+    /// Checks if the [crossword](Crossword) can still satisfy the [constraints](CrosswordSizeConstraint) after the remaining words are added to it, see [CrosswordConstraint::can_be_extended_with]
+    pub fn can_be_extended_with(&self, cw: &Crossword, remaining_words: &[&str]) -> bool
+    {
+        self.size_constraints.iter().all(|c| c.can_be_extended(cw)) && self.constraints.iter().all(|c| c.can_be_extended_with(cw, remaining_words))
+    }
+
+    /// Returns the [cell letter constraints](CellLetterConstraint) that have to be satisfied, the ones in [constraints](CrosswordSettings::constraints) and in their [And](CombinedConstraint::And) combinations
+    pub fn get_cell_letter_constraints(&self) -> Vec<&CellLetterConstraint>
+    {
+        fn collect<'c>(constraint: &'c CombinedConstraint, result: &mut Vec<&'c CellLetterConstraint>)
+        {
+            match constraint
+            {
+                CombinedConstraint::CellLetter(c) => result.push(c),
+                CombinedConstraint::And(cs) => cs.iter().for_each(|c| collect(c, result)),
+                _ => {}
+            }
+        }
+
+        let mut result = vec![];
+        self.constraints.iter().for_each(|c| collect(c, &mut result));
+        result
+    }
+}
+
+/// Represents an error that occurred while reading a [crossword](Crossword) from a grid with [from_grid_str](Crossword::from_grid_str)
+///
+/// Line numbers start from 1, cell coordinates start from 0 at the top left cell of the grid.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum GridParseError
+{
+    /// The grid has no letters in it
+    Empty,
+    /// A border line of a boxed grid is missing or contains something other than '-'
+    MalformedBorder{ line: usize },
+    /// A row of a boxed grid is not enclosed in '|', has a different width than the border, or has a non space character between cells
+    MalformedRow{ line: usize },
+    /// Two parallel runs of letters, given by the cells they start at, touch side by side, so it can't be told which letters form words
+    SideBySideWords{ first: (usize, usize), second: (usize, usize) },
+}
+
+impl std::fmt::Display for GridParseError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            GridParseError::Empty => write!(f, "the grid has no letters"),
+            GridParseError::MalformedBorder{ line } => write!(f, "line {}: expected a border line made of '-'", line),
+            GridParseError::MalformedRow{ line } => write!(f, "line {}: expected a row of cells separated by spaces and enclosed in '|' matching the border width", line),
+            GridParseError::SideBySideWords{ first, second } => write!(f, "the words starting at cells {:?} and {:?} lie side by side, so the words of the grid are ambiguous", first, second),
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// Represents an error of an edit of a [crossword](Crossword), returned by [try_add_word](Crossword::try_add_word), [try_remove_word](Crossword::try_remove_word) and [try_move_word](Crossword::try_move_word)
+///
+/// Words are given by their string value and [id](Word::id).
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum CrosswordError
+{
+    /// The crossword already has a word with the same string value and id
+    DuplicateWord{ value: String, id: usize },
+    /// The word has a different letter than the other word in a common cell
+    LetterConflict{ position: WordPosition, letter: char, other_value: String, other_id: usize, other_letter: char },
+    /// The word is placed in a way the [word compatibility](WordCompatibility) doesn't allow next to the other word, for example side by side
+    IncompatibleWord{ value: String, id: usize, other_value: String, other_id: usize },
+    /// The crossword has no word with the string value and id
+    WordNotFound{ value: String, id: usize },
+}
+
+impl std::fmt::Display for CrosswordError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            CrosswordError::DuplicateWord{ value, id } => write!(f, "the word \"{}\" (id {}) is already in the crossword", value, id),
+            CrosswordError::LetterConflict{ position, letter, other_value, other_id, other_letter } => write!(f, "the letter '{}' at ({}, {}) conflicts with the letter '{}' of the word \"{}\" (id {})", letter, position.x, position.y, other_letter, other_value, other_id),
+            CrosswordError::IncompatibleWord{ value, id, other_value, other_id } => write!(f, "the word \"{}\" (id {}) can't be placed like this next to the word \"{}\" (id {})", value, id, other_value, other_id),
+            CrosswordError::WordNotFound{ value, id } => write!(f, "the word \"{}\" (id {}) is not in the crossword", value, id),
+        }
+    }
+}
+
+impl std::error::Error for CrosswordError {}
+
+/// Statistics of a [crossword](Crossword), returned by [get_stats](Crossword::get_stats)
+///
+/// ## Fields
+///
+/// across, down, diagonal -> numbers of [horizontal](WordDirection::is_horizontal), [vertical](WordDirection::is_vertical) and diagonal [words](Word)
+///
+/// size -> the [size](Crossword::get_size) of the crossword
+///
+/// letter_cells -> the number of cells with letters, see [get_cell_count](Crossword::get_cell_count)
+///
+/// total_length -> the sum of the lengths of all words
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct CrosswordStats
+{
+    pub across: usize,
+    pub down: usize,
+    pub diagonal: usize,
+    pub size: (usize, usize),
+    pub letter_cells: usize,
+    pub total_length: usize,
+}
+
+impl CrosswordStats
+{
+    /// Returns the number of [words](Word)
+    pub fn get_word_count(&self) -> usize
+    {
+        self.across + self.down + self.diagonal
+    }
+
+    /// Returns the percentage of the cells of the bounding rectangle that have letters, 0 for an empty crossword
+    pub fn get_density(&self) -> f64
+    {
+        let area = self.size.0 * self.size.1;
+        if area == 0 { 0.0 } else { 100.0 * self.letter_cells as f64 / area as f64 }
+    }
+
+    /// Returns the number of letters shared by crossing [words](Word), a cell shared by n words counts n - 1 times
+    pub fn get_intersection_count(&self) -> usize
+    {
+        self.total_length - self.letter_cells
     }
+
+    /// Returns the average length of the [words](Word), 0 for an empty crossword
+    pub fn get_average_word_length(&self) -> f64
+    {
+        let count = self.get_word_count();
+        if count == 0 { 0.0 } else { self.total_length as f64 / count as f64 }
+    }
+}
+
+/// Returns a [char table](Crossword::generate_char_table) in a box made of '-' and '|', with a space between the cells
+pub(crate) fn format_char_table(table: Vec<Vec<char>>) -> String
+{
+    let size = table.first().map_or(0, |row| row.len()) * 2 + 1;
+    let result: String = [vec!['-'; size], vec!['\n']].concat().into_iter().chain(table
+        .into_iter()
+        .flat_map(|mut el| 
+        {
+            el = el.into_iter().flat_map(|ch| [ch, ' ']).collect();
+            el.insert(0, '|');
+            el.pop();
+            el.push('|');
+            el.push('\n');
+            el
+        })).chain([vec!['-'; size], vec!['\n']].concat())
+        .collect();
+
+
+    result
 }
 
 /// # Represents a crossword
-/// 
-/// A crossword can't have two [words](Word) with the same string value in it.
+///
+/// A crossword can have several [words](Word) with the same string value in it, the copies are told apart by their [id](Word::id).
+///
+/// A crossword is either relative, its words are [normalized](Crossword::normalize) after every change, or absolute (created with [new_absolute](Crossword::new_absolute)),
+/// its words keep their coordinates, for example the coordinates on a [board](crate::board::Board).
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 pub struct Crossword<'a>
 {
     #[serde(borrow)]
     words: BTreeSet<Word<'a>>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    absolute: bool,
 }
 
 impl<'a> Crossword<'a>
@@ -89,6 +340,37 @@ impl<'a> Crossword<'a>
         return cw;
     }
 
+    /// Creates a new absolute [crossword](Crossword) with the given [words](Word), the coordinates of its words are never [normalized](Crossword::normalize) by [add_word](Crossword::add_word) and [remove_word](Crossword::remove_word)
+    ///
+    /// Coordinates should not be negative, [get_size](Crossword::get_size) and [generate_char_table](Crossword::generate_char_table) start from the origin.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let mut cw = Crossword::new_absolute(&[                                                                    //     -----
+    ///     Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "hi", id: 0},      //    |     |
+    /// ]);                                                                                                        //    |  h i|
+    ///                                                                                                            //     -----
+    /// assert!(cw.is_absolute());
+    /// assert_eq!(cw.get_size(), (3, 2));
+    ///
+    /// cw.remove_word("hi");
+    /// cw.add_word(&Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "ok", id: 0});
+    /// assert_eq!(cw.find_word("ok").unwrap().position, WordPosition { x: 2, y: 0 });
+    /// ```
+    pub fn new_absolute(words: &[Word<'a>]) -> Crossword<'a>
+    {
+        Crossword { words: words.iter().cloned().collect(), absolute: true }
+    }
+
+    /// Checks if the [crossword](Crossword) is absolute, see [new_absolute](Crossword::new_absolute)
+    pub fn is_absolute(&self) -> bool
+    {
+        self.absolute
+    }
+
     /// Moves [words'](Word) coordinates so that the minimum x and y coordinates are zeros
     /// 
     /// ## Example
@@ -96,25 +378,25 @@ impl<'a> Crossword<'a>
     /// ```
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
     /// # use crossword_generator::crossword::Crossword;                                                      
-    ///                                                                                                      //       0
-    ///                                                                                                      //       |
-    /// let mut cw = Crossword::new(&[                                                                       //     ---------
-    ///     Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},  //    |h e l l o|
-    ///     Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},    //0 - |    o    |
-    ///     Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},      //    |    c a t|
-    ///     Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},       //    |    a n o|
-    ///     Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},       //    |    l d y|
-    /// ]);                                                                                                  //     ---------
+    ///                                                                                                             //       0
+    ///                                                                                                             //       |
+    /// let mut cw = Crossword::new(&[                                                                              //     ---------
+    ///     Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},  //    |h e l l o|
+    ///     Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},    //0 - |    o    |
+    ///     Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},      //    |    c a t|
+    ///     Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},       //    |    a n o|
+    ///     Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},       //    |    l d y|
+    /// ]);                                                                                                         //     ---------
     /// cw.normalize();                                                                                         
-    ///                                                                                                      //     0
-    ///                                                                                                      //     | 
-    /// let cw_normalized = Crossword::new(&[                                                                //     ---------                 
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},    //0 - |h e l l o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},     //    |    o    |
-    ///     Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat"},      //    |    c a t|
-    ///     Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and"},       //    |    a n o|
-    ///     Word{position: WordPosition { x: 4, y: 2 }, direction: WordDirection::Down, value: "toy"},       //    |    l d y|
-    /// ]);                                                                                                  //     ---------
+    ///                                                                                                             //     0
+    ///                                                                                                             //     | 
+    /// let cw_normalized = Crossword::new(&[                                                                       //     ---------                 
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},    //0 - |h e l l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},     //    |    o    |
+    ///     Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},      //    |    c a t|
+    ///     Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and", id: 0},       //    |    a n o|
+    ///     Word{position: WordPosition { x: 4, y: 2 }, direction: WordDirection::Down, value: "toy", id: 0},       //    |    l d y|
+    /// ]);                                                                                                         //     ---------
     ///     
     /// assert_eq!(cw, cw_normalized);
     /// ```
@@ -125,8 +407,9 @@ impl<'a> Crossword<'a>
 
         for word in self.words.iter()
         {
-            min_corner.0 = min_corner.0.min(word.position.x);
-            min_corner.1 = min_corner.1.min(word.position.y);
+            let (first, _) = word.get_corners();
+            min_corner.0 = min_corner.0.min(first.x);
+            min_corner.1 = min_corner.1.min(first.y);
         }
 
         for word in self.words.iter()
@@ -137,7 +420,7 @@ impl<'a> Crossword<'a>
         self.words = new_set;
     }
 
-    /// Adds the [word](Word) to the [crossword](Crossword) if not finded any other word with same string value and [normalizes](Crossword::normalize) it
+    /// Adds the [word](Word) to the [crossword](Crossword) if not finded any other word with same string value and [id](Word::id) and [normalizes](Crossword::normalize) it, unless it is [absolute](Crossword::new_absolute)
     /// 
     /// ## Warning
     /// Note that this function does not check if the specified word can be added correctly.
@@ -145,83 +428,226 @@ impl<'a> Crossword<'a>
     /// For checking if a word can be added to the crossword correctly, you need to use the function [can_word_be_added](Crossword::can_word_be_added).
     pub fn add_word(&mut self, word: &Word<'a>)
     {
-        if self.words.iter().find(|w| w.value == word.value).is_some() { return; }
+        if self.find_word_with_id(word.value, word.id).is_some() { return; }
         self.words.insert(word.clone());
-        self.normalize();
+        if !self.absolute { self.normalize(); }
     }
 
-    /// Removes the [word](Word) from the [crossword](Crossword) if finded and [normalizes](Crossword::normalize) it
+    /// Removes the [word](Word) from the [crossword](Crossword) if finded and [normalizes](Crossword::normalize) it, unless it is [absolute](Crossword::new_absolute)
+    ///
+    /// If there are several copies of the word, the one [find_word](Crossword::find_word) returns is removed, use [remove_word_with_id](Crossword::remove_word_with_id) to pick the copy.
     pub fn remove_word(&mut self, word: &str)
     {
-        if let Some(word) = self.find_word(word).and_then(|w: &Word<'a>| Some(w.clone()))
+        if let Some(word) = self.find_word(word).cloned()
+        {
+            self.remove_word_with_id(word.value, word.id);
+        }
+    }
+
+    /// Removes the copy of the [word](Word) with the given [id](Word::id) from the [crossword](Crossword) if finded and [normalizes](Crossword::normalize) it, unless it is [absolute](Crossword::new_absolute)
+    pub fn remove_word_with_id(&mut self, word: &str, id: usize)
+    {
+        if let Some(word) = self.find_word_with_id(word, id).cloned()
         {
             self.words.remove(&word);
 
-            self.normalize();
+            if !self.absolute { self.normalize(); }
         }
     }
 
-    /// Finds the [word](Word) given its string value.
+    /// Finds the [word](Word) given its string value, the copy with the lowest [id](Word::id) if there are several.
     pub fn find_word(&self, word: &str) -> Option<&Word<'a>>
     {
-        self.words.iter().filter(|w| w.value == word).next()
+        self.words.iter().filter(|w| w.value == word).min_by_key(|w| w.id)
     }
-    
+
+    /// Finds the copy of the [word](Word) given its string value and [id](Word::id).
+    pub fn find_word_with_id(&self, word: &str, id: usize) -> Option<&Word<'a>>
+    {
+        self.words.iter().find(|w| w.value == word && w.id == id)
+    }
+
+    /// Returns all copies of the [word](Word) with the given string value
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let mut cw = Crossword::new(&[                                                                             //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},      //    |    o|
+    ///     Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "cat", id: 1},     //    |c a y|
+    /// ]);                                                                                                        //     -----
+    ///
+    /// assert_eq!(cw.find_words("cat").count(), 2);
+    /// assert_eq!(cw.find_word("cat").unwrap().position, WordPosition { x: 0, y: 0 });
+    ///
+    /// cw.remove_word_with_id("cat", 0);
+    /// assert_eq!(cw.find_word("cat").unwrap().id, 1);
+    /// ```
+    pub fn find_words<'b>(&'b self, word: &'b str) -> impl Iterator<Item = &'b Word<'a>> + 'b
+    {
+        self.words.iter().filter(move |w| w.value == word)
+    }
+
+    /// Gives the copies of every [word](Word) [ids](Word::id) 0, 1, 2, ... in the order of their positions
+    ///
+    /// Crosswords that only differ in which copy of a word is placed where become equal.
+    pub fn renumber_duplicates(&mut self)
+    {
+        let mut counts: BTreeMap<&'a str, usize> = BTreeMap::new();
+        self.words = self.words.iter()
+            .map(|w|
+            {
+                let count = counts.entry(w.value).or_default();
+                let word = Word{ id: *count, ..w.clone() };
+                *count += 1;
+                word
+            })
+            .collect();
+    }
+
+    /// Returns the letter in the cell, or None if no [word](Word) goes through it
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cow", id: 0},      //    |o    |
+    /// ]);                                                                                                        //    |w    |
+    ///                                                                                                            //     -----
+    /// assert_eq!(cw.get_letter(&WordPosition { x: 0, y: 1 }), Some('o'));
+    /// assert_eq!(cw.get_letter(&WordPosition { x: 1, y: 1 }), None);
+    /// ```
+    pub fn get_letter(&self, position: &WordPosition) -> Option<char>
+    {
+        self.words.iter()
+            .flat_map(|w| w.get_cells())
+            .find(|(p, _)| p == position)
+            .map(|(_, ch)| ch)
+    }
+
+    /// Returns the letters in the cells of the [cell letter constraints](CellLetterConstraint) of the settings, in the order of the constraints
+    ///
+    /// For a hidden message, the letters spell it once all constraints are satisfied.
+    pub fn get_constrained_letters(&self, settings: &CrosswordSettings) -> Vec<(WordPosition, Option<char>)>
+    {
+        settings.get_cell_letter_constraints().into_iter()
+            .map(|c| (c.position.clone(), self.get_letter(&c.position)))
+            .collect()
+    }
+
+    /// Returns all [words](Word) of the [crossword](Crossword)
+    pub fn get_words(&self) -> &BTreeSet<Word<'a>>
+    {
+        &self.words
+    }
+
+    /// Returns the [words](Word) of the [crossword](Crossword) together with their clue numbers
+    ///
+    /// Cells where at least one word starts are numbered from 1 in reading order (row by row, left to right),
+    /// and words starting on the same cell share the number. The result is sorted by number, with the [right](WordDirection::Right) word first.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let cw = Crossword::new(&[                                                                                 //     ---------
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},   //    |1 e 2 l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    ///     Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |    3 a t|
+    /// ]);                                                                                                        //    |    a    |
+    ///                                                                                                            //    |    l    |
+    ///                                                                                                            //     ---------
+    ///
+    /// let numbers: Vec<(usize, &str)> = cw.get_numbered_words().into_iter().map(|(n, w)| (n, w.value)).collect();
+    /// assert_eq!(numbers, vec![(1, "hello"), (2, "local"), (3, "cat")]);
+    /// ```
+    pub fn get_numbered_words(&self) -> Vec<(usize, &Word<'a>)>
+    {
+        let mut words: Vec<&Word<'a>> = self.words.iter().collect();
+        words.sort_by_key(|w| (w.position.y, w.position.x, w.direction.clone()));
+
+        let mut result = vec![];
+        let mut last_position: Option<&WordPosition> = None;
+        let mut number = 0;
+        for word in words
+        {
+            if last_position != Some(&word.position)
+            {
+                number += 1;
+                last_position = Some(&word.position);
+            }
+            result.push((number, word));
+        }
+
+        result
+    }
+
+    /// Returns the clue numbers of the [numbered words](Crossword::get_numbered_words) by the (column, row) of their first cell in the [char table](Crossword::generate_char_table)
+    pub fn get_cell_numbers(&self) -> BTreeMap<(usize, usize), usize>
+    {
+        let min_corner = self.get_min_corner();
+        self.get_numbered_words().iter()
+            .map(|(n, w)| (((w.position.x - min_corner.x) as usize, (w.position.y - min_corner.y) as usize), *n))
+            .collect()
+    }
+
     /// Checks if another [crossword](Crossword) is found inside this crossword.
+    ///
+    /// If one of the crosswords is [absolute](Crossword::new_absolute), the words have to be on the same coordinates, otherwise the other crossword can be moved.
+    /// The [ids](Word::id) of the words are not compared, any copy of a word can match.
     /// 
     /// ## Example
     /// 
     /// ```
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
     /// # use crossword_generator::crossword::Crossword;                                                      
-    /// let mut cw1 = Crossword::new(&[                                                                      //     ---------
-    ///     Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},  //    |h e l l o|
-    ///     Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},    //    |    o    |
-    ///     Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},      //    |    c a t|
-    ///     Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},       //    |    a n o|
-    ///     Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},       //    |    l d y|
-    /// ]);                                                                                                  //     ---------
+    /// let mut cw1 = Crossword::new(&[                                                                             //     ---------
+    ///     Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},  //    |h e l l o|
+    ///     Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    ///     Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},      //    |    c a t|
+    ///     Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},       //    |    a n o|
+    ///     Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},       //    |    l d y|
+    /// ]);                                                                                                         //     ---------
     ///                                                                                         
     ///
-    /// let cw2 = Crossword::new(&[                                                                          //     -----                 
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat"},      //    |c a t|
-    ///     Word{position: WordPosition { x: 1, y: 0 }, direction: WordDirection::Down, value: "and"},       //    |  n o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy"},       //    |  d y|
-    /// ]);                                                                                                  //     -----
+    /// let cw2 = Crossword::new(&[                                                                                 //     -----                 
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},      //    |c a t|
+    ///     Word{position: WordPosition { x: 1, y: 0 }, direction: WordDirection::Down, value: "and", id: 0},       //    |  n o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},       //    |  d y|
+    /// ]);                                                                                                         //     -----
     ///     
     /// assert!(cw1.contains_crossword(&cw2));
     /// ```
     pub fn contains_crossword(&self, other: &Crossword) -> bool 
     {
         if other.words.len() > self.words.len() { return false; }
-        let mut offset: Option<(isize, isize)> = None;
-        for other_word in other.words.iter()
+        let first_word = match other.words.iter().next()
         {
-            let cur_word = self.find_word(&other_word.value);
-            if let None = cur_word
-            {
-                return false;
-            }
-            let cur_word = cur_word.unwrap();
-            if cur_word.direction != other_word.direction
-            {
-                return false;
-            }
+            Some(word) => word,
+            None => return true
+        };
 
-            match &offset
-            {
-                None => offset = Some((cur_word.position.x - other_word.position.x, cur_word.position.y - other_word.position.y)),
-                Some(offset) => 
-                {
-                    if *offset != (cur_word.position.x - other_word.position.x, cur_word.position.y - other_word.position.y)
-                    {
-                        return false;
-                    }
-                }
-            }
+        let offsets: Vec<(isize, isize)> = match self.absolute || other.absolute
+        {
+            true => vec![(0, 0)],
+            false => self.find_words(first_word.value)
+                .filter(|w| w.direction == first_word.direction)
+                .map(|w| (w.position.x - first_word.position.x, w.position.y - first_word.position.y))
+                .collect()
+        };
 
-        }
-        true
+        offsets.into_iter().any(|(dx, dy)| other.words.iter().all(|other_word|
+        {
+            let position = WordPosition { x: other_word.position.x + dx, y: other_word.position.y + dy };
+            self.find_words(other_word.value).any(|w| w.direction == other_word.direction && w.position == position)
+        }))
     }
 
     /// Returns all possible ways (given some [settings](WordCompatibilitySettings)) to add a [word](Word) into the [crossword](Crossword)
@@ -232,62 +658,194 @@ impl<'a> Crossword<'a>
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
     /// # use crossword_generator::crossword::Crossword;         
     /// # use std::collections::BTreeSet;                                             
-    /// let mut cw = Crossword::new(&[                                                                      //     ---------
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},   //    |h e l l o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},    //    |    o    |
-    /// ]);                                                                                                 //    |    c    |
-    ///                                                                                                     //    |    a    |
-    ///                                                                                                     //    |    l    |
-    ///                                                                                                     //     ---------
+    /// let mut cw = Crossword::new(&[                                                                             //     ---------
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},   //    |h e l l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    /// ]);                                                                                                        //    |    c    |
+    ///                                                                                                            //    |    a    |
+    ///                                                                                                            //    |    l    |
+    ///                                                                                                            //     ---------
     ///                                                                                             
     /// assert_eq!(cw.calculate_possible_ways_to_add_word("halo", &WordCompatibilitySettings::default()), 
     ///             BTreeSet::from([
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "halo"},
-    ///     Word{position: WordPosition { x: 4, y: -3 }, direction: WordDirection::Down, value: "halo"},
-    ///     Word{position: WordPosition { x: 0, y: 4 }, direction: WordDirection::Right, value: "halo"},
-    ///     Word{position: WordPosition { x: 1, y: 3 }, direction: WordDirection::Right, value: "halo"},
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "halo", id: 0},
+    ///     Word{position: WordPosition { x: 4, y: -3 }, direction: WordDirection::Down, value: "halo", id: 0},
+    ///     Word{position: WordPosition { x: 0, y: 4 }, direction: WordDirection::Right, value: "halo", id: 0},
+    ///     Word{position: WordPosition { x: 1, y: 3 }, direction: WordDirection::Right, value: "halo", id: 0},
     /// ]));
     /// ```
     /// 
     /// 
     /// 
     /// Note that for example word halo on position 3 -2 and direction down is not allowed by a setting in word compatibility settings that forbids two words with same direction to be side to side
-    pub fn calculate_possible_ways_to_add_word(&self, word: &'a str, word_compatibility_settings: &WordCompatibilitySettings) -> BTreeSet<Word<'a>>
+    pub fn calculate_possible_ways_to_add_word<C: WordCompatibility + ?Sized>(&self, word: &'a str, word_compatibility_settings: &C) -> BTreeSet<Word<'a>>
+    {
+        self.calculate_possible_ways_to_add_word_in_directions(word, word_compatibility_settings, &DEFAULT_DIRECTIONS)
+    }
+
+    /// Returns all possible ways to add a [word](Word) into the [crossword](Crossword) in one of the directions, see [calculate_possible_ways_to_add_word](Crossword::calculate_possible_ways_to_add_word)
+    ///
+    /// The first word of an empty crossword is placed at (0, 0) in every direction of the list that is not vertical (or every direction if all are vertical),
+    /// because every crossword with crossing words has a word that is not vertical.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
+    /// # use crossword_generator::crossword::Crossword;
+    /// # use std::collections::BTreeSet;
+    /// let cw = Crossword::new(&[                                                                                 //     ---------
+    ///     Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Left, value: "olleh", id: 0},    //    |h e l l o|
+    /// ]);                                                                                                        //    |        n|
+    ///                                                                                                            //     ---------
+    ///
+    /// assert_eq!(cw.calculate_possible_ways_to_add_word_in_directions("on", &WordCompatibilitySettings::default(), &[WordDirection::Left, WordDirection::Down]),
+    ///             BTreeSet::from([
+    ///     Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Down, value: "on", id: 0},
+    /// ]));
+    /// ```
+    pub fn calculate_possible_ways_to_add_word_in_directions<C: WordCompatibility + ?Sized>(&self, word: &'a str, word_compatibility_settings: &C, directions: &[WordDirection]) -> BTreeSet<Word<'a>>
     {
         if self.words.is_empty()
         {
-            return vec![Word{ value: word, ..Word::default()}].into_iter().collect()
+            let not_vertical: Vec<&WordDirection> = directions.iter().filter(|d| !d.is_vertical()).collect();
+            let first_directions = if not_vertical.is_empty() { directions.iter().collect() } else { not_vertical };
+            return first_directions.into_iter().map(|direction| Word{ value: word, direction: direction.clone(), ..Word::default()}).collect()
         }
 
         self.words.iter()
-            .flat_map(|cur_word: &Word<'a>| cur_word.calculate_possible_ways_to_add_word(word))
+            .flat_map(|cur_word: &Word<'a>| cur_word.calculate_possible_ways_to_add_word_in_directions(word, directions))
             .filter(|w: &Word<'a>| self.can_word_be_added(w, word_compatibility_settings))
             .collect()
     }
 
-    /// Checks if a [word](Word) can be added to the [crossword](Crossword) given the [word compatibility settings](WordCompatibilitySettings)
+    /// Checks if a [word](Word) can be added to the [crossword](Crossword) given the [word compatibility settings](WordCompatibilitySettings) or another [word compatibility](WordCompatibility)
     /// 
     /// ## Example
     /// 
     /// ```
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
     /// # use crossword_generator::crossword::Crossword;                                         
-    /// let mut cw = Crossword::new(&[                                                                      //     ---------
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},   //    |h e l l o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},    //    |    o    |
-    /// ]);                                                                                                 //    |    c    |
-    ///                                                                                                     //    |    a    |
-    ///                                                                                                     //    |    l    |
-    ///                                                                                                     //     ---------
+    /// let mut cw = Crossword::new(&[                                                                             //     ---------
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},   //    |h e l l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    /// ]);                                                                                                        //    |    c    |
+    ///                                                                                                            //    |    a    |
+    ///                                                                                                            //    |    l    |
+    ///                                                                                                            //     ---------
     ///                                                                                             
-    /// assert!(cw.can_word_be_added(&Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "halo"}, &WordCompatibilitySettings::default()));
+    /// assert!(cw.can_word_be_added(&Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "halo", id: 0}, &WordCompatibilitySettings::default()));
     /// ```
     /// 
     /// Note that for example word halo on position 3 -2 and direction down is not allowed by a setting in word compatibility settings that forbids two words with same direction to be side to side
-    pub fn can_word_be_added(&self, word: &Word<'a>, word_compatibility_settings: &WordCompatibilitySettings) -> bool
+    pub fn can_word_be_added<C: WordCompatibility + ?Sized>(&self, word: &Word<'a>, word_compatibility_settings: &C) -> bool
     {
         self.words.iter().all(|w: &Word<'a>| word_compatibility_settings.are_words_compatible(w, word))
     }
+
+    /// Checks if the [word](Word) can be added, like [can_word_be_added](Crossword::can_word_be_added), and returns the reason if it can't
+    ///
+    /// Conflicting letters are reported before other [compatibility](WordCompatibility) problems.
+    fn check_word<C: WordCompatibility + ?Sized>(&self, word: &Word<'a>, word_compatibility: &C) -> Result<(), CrosswordError>
+    {
+        if self.find_word_with_id(word.value, word.id).is_some()
+        {
+            return Err(CrosswordError::DuplicateWord{ value: word.value.to_owned(), id: word.id });
+        }
+
+        let cells = word.get_cells();
+        for other in self.words.iter()
+        {
+            for (position, other_letter) in other.get_cells()
+            {
+                if let Some((_, letter)) = cells.iter().find(|(p, ch)| *p == position && *ch != other_letter)
+                {
+                    return Err(CrosswordError::LetterConflict{ position, letter: *letter, other_value: other.value.to_owned(), other_id: other.id, other_letter });
+                }
+            }
+        }
+
+        match self.words.iter().find(|other| !word_compatibility.are_words_compatible(other, word))
+        {
+            Some(other) => Err(CrosswordError::IncompatibleWord{ value: word.value.to_owned(), id: word.id, other_value: other.value.to_owned(), other_id: other.id }),
+            None => Ok(())
+        }
+    }
+
+    /// Adds the [word](Word) to the [crossword](Crossword) like [add_word](Crossword::add_word) if it [can be added](Crossword::can_word_be_added), otherwise returns the reason and leaves the crossword unchanged
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
+    /// # use crossword_generator::crossword::{Crossword, CrosswordError};
+    /// let mut cw = Crossword::new(&[                                                                             //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    /// ]);                                                                                                        //     -----
+    /// let settings = WordCompatibilitySettings::default();
+    ///
+    /// assert_eq!(cw.try_add_word(&Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0}, &settings), Ok(()));
+    /// assert_eq!(cw.try_add_word(&Word{position: WordPosition { x: 1, y: 0 }, direction: WordDirection::Down, value: "owl", id: 0}, &settings),
+    ///     Err(CrosswordError::LetterConflict{ position: WordPosition { x: 1, y: 0 }, letter: 'o', other_value: "cat".to_owned(), other_id: 0, other_letter: 'a' }));
+    /// assert_eq!(cw.try_add_word(&Word{position: WordPosition { x: 1, y: 0 }, direction: WordDirection::Down, value: "ant", id: 0}, &settings),
+    ///     Err(CrosswordError::IncompatibleWord{ value: "ant".to_owned(), id: 0, other_value: "toy".to_owned(), other_id: 0 }));
+    /// assert_eq!(cw.get_words().len(), 2);
+    /// ```
+    pub fn try_add_word<C: WordCompatibility + ?Sized>(&mut self, word: &Word<'a>, word_compatibility: &C) -> Result<(), CrosswordError>
+    {
+        self.check_word(word, word_compatibility)?;
+        self.add_word(word);
+        Ok(())
+    }
+
+    /// Removes the copy of the [word](Word) with the given [id](Word::id) like [remove_word_with_id](Crossword::remove_word_with_id) and returns it, or an error if it is not in the [crossword](Crossword)
+    pub fn try_remove_word(&mut self, word: &str, id: usize) -> Result<Word<'a>, CrosswordError>
+    {
+        let removed = self.find_word_with_id(word, id).cloned().ok_or_else(|| CrosswordError::WordNotFound{ value: word.to_owned(), id })?;
+        self.remove_word_with_id(word, id);
+        Ok(removed)
+    }
+
+    /// Moves the word with the string value and [id](Word::id) of the given [word](Word) to its position and direction, and returns the word as it was before the move
+    ///
+    /// The position is in the coordinates of the [crossword](Crossword) before the move, the crossword is [normalized](Crossword::normalize) afterwards unless it is [absolute](Crossword::new_absolute).
+    /// If the word is not found or can't be placed at the new position, the crossword is left unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
+    /// # use crossword_generator::crossword::{Crossword, CrosswordError};
+    /// let mut cw = Crossword::new(&[                                                                             //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},      //    |    o|
+    /// ]);                                                                                                        //    |    t|
+    ///                                                                                                            //     -----
+    /// let settings = WordCompatibilitySettings::default();
+    ///
+    /// let before = cw.try_move_word(&Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Down, value: "tot", id: 0}, &settings);
+    /// assert_eq!(before.unwrap().position, WordPosition { x: 2, y: 0 });                                         //     -----
+    /// assert_eq!(cw.find_word("cat").unwrap().position, WordPosition { x: 0, y: 2 });                            //    |    t|
+    ///                                                                                                            //    |    o|
+    ///                                                                                                            //    |c a t|
+    ///                                                                                                            //     -----
+    ///
+    /// assert!(matches!(cw.try_move_word(&Word{value: "dog", ..Word::default()}, &settings), Err(CrosswordError::WordNotFound{ .. })));
+    /// ```
+    pub fn try_move_word<C: WordCompatibility + ?Sized>(&mut self, word: &Word<'a>, word_compatibility: &C) -> Result<Word<'a>, CrosswordError>
+    {
+        let previous = self.find_word_with_id(word.value, word.id).cloned().ok_or_else(|| CrosswordError::WordNotFound{ value: word.value.to_owned(), id: word.id })?;
+        self.words.remove(&previous);
+
+        if let Err(error) = self.check_word(word, word_compatibility)
+        {
+            self.words.insert(previous);
+            return Err(error);
+        }
+
+        self.add_word(word);
+        Ok(previous)
+    }
     
     /// Returns the size of the minimum rectangle that can contain the [crossword](Crossword)
     /// 
@@ -296,34 +854,180 @@ impl<'a> Crossword<'a>
     /// ```
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
     /// # use crossword_generator::crossword::Crossword;                                         
-    /// let mut cw = Crossword::new(&[                                                                      //     ---------
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},   //    |h e l l o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},    //    |    o    |
-    /// ]);                                                                                                 //    |    c    |
-    ///                                                                                                     //    |    a    |
-    ///                                                                                                     //    |    l    |
-    ///                                                                                                     //     ---------
+    /// let mut cw = Crossword::new(&[                                                                             //     ---------
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},   //    |h e l l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    /// ]);                                                                                                        //    |    c    |
+    ///                                                                                                            //    |    a    |
+    ///                                                                                                            //    |    l    |
+    ///                                                                                                            //     ---------
     ///                                                                                             
     /// assert_eq!(cw.get_size(), (5, 5));
     /// ```
     pub fn get_size(&self) -> (usize, usize)
     {
+        let min_corner = self.get_min_corner();
         let mut max_corner = (0isize, 0isize);
     
         for word in self.words.iter()
         {
-            max_corner.0 = max_corner.0.max(word.position.x + 1);
-            max_corner.1 = max_corner.1.max(word.position.y + 1);
-            match word.direction
+            let (_, last) = word.get_corners();
+            max_corner.0 = max_corner.0.max(last.x + 1);
+            max_corner.1 = max_corner.1.max(last.y + 1);
+        }
+    
+        ((max_corner.0 - min_corner.x) as usize, (max_corner.1 - min_corner.y) as usize)
+    }
+
+    /// Returns the position of the top left cell of the [char table](Crossword::generate_char_table) of the [crossword](Crossword)
+    ///
+    /// The table starts at the origin, unless an [absolute](Crossword::new_absolute) crossword has cells with negative coordinates,
+    /// then it starts at the smallest ones, so the cell at a position is in the table at (x - min_corner.x, y - min_corner.y).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let cw = Crossword::new_absolute(&[                                                                        //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |    t|
+    ///     Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Down, value: "tot", id: 0},     //    |    o|
+    /// ]);                                                                                                        //    |c a t|
+    ///                                                                                                            //     -----
+    /// assert_eq!(cw.get_min_corner(), WordPosition { x: 0, y: -2 });
+    /// assert_eq!(cw.get_size(), (3, 3));
+    /// ```
+    pub fn get_min_corner(&self) -> WordPosition
+    {
+        let mut min_corner = WordPosition { x: 0, y: 0 };
+
+        for word in self.words.iter()
+        {
+            let (first, _) = word.get_corners();
+            min_corner.x = min_corner.x.min(first.x);
+            min_corner.y = min_corner.y.min(first.y);
+        }
+
+        min_corner
+    }
+
+    /// Returns the number of cells with letters in the [crossword](Crossword), shared cells of crossing words are counted once
+    pub fn get_cell_count(&self) -> usize
+    {
+        self.generate_char_table().iter().flatten().filter(|ch| **ch != ' ').count()
+    }
+
+    /// Returns the number of [words](Word) of the [crossword](Crossword) that cross the word
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let cat = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0};
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     cat.clone(),                                                                                           //    |c a t|
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cow", id: 0},      //    |o   o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toe", id: 0},      //    |w   e|
+    /// ]);                                                                                                        //     -----
+    ///
+    /// assert_eq!(cw.get_crossing_count(&cat), 2);
+    /// assert_eq!(cw.get_checked_cell_count(), 2);
+    /// ```
+    pub fn get_crossing_count(&self, word: &Word) -> usize
+    {
+        self.words.iter().filter(|w| w.get_intersection_indices(word).is_some()).count()
+    }
+
+    /// Returns the number of cells shared by two or more crossing [words](Word), a cell where three words cross is counted once
+    pub fn get_checked_cell_count(&self) -> usize
+    {
+        let mut counts: BTreeMap<WordPosition, usize> = BTreeMap::new();
+        for (position, _) in self.words.iter().flat_map(|w| w.get_cells())
+        {
+            *counts.entry(position).or_default() += 1;
+        }
+        counts.values().filter(|count| **count > 1).count()
+    }
+
+    /// Checks if every [word](Word) can be reached from every other one by going from a word to a word crossing it, an empty crossword is connected
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let mut cw = Crossword::new_absolute(&[                                                                     //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},      //    |c a t|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toe", id: 0},       //    |    o|
+    /// ]);                                                                                                         //    |    e|
+    /// assert!(cw.is_connected());                                                                                 //     -----
+    ///
+    /// cw.add_word(&Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Down, value: "ox", id: 0});
+    /// assert!(!cw.is_connected());
+    /// ```
+    pub fn is_connected(&self) -> bool
+    {
+        self.is_connected_by(|first, second| first.get_intersection_indices(second).is_some())
+    }
+
+    /// Checks if the crossword is [connected](Crossword::is_connected) when two [words](Word) cross each other as the function tells,
+    /// for example on a [board](crate::board::Board) with [do_words_cross](crate::board::Board::do_words_cross)
+    pub fn is_connected_by<F: Fn(&Word, &Word) -> bool>(&self, do_words_cross: F) -> bool
+    {
+        let words: Vec<&Word> = self.words.iter().collect();
+        if words.is_empty() { return true; }
+
+        let mut connected = BTreeSet::from([0]);
+        let mut stack = vec![0];
+        while let Some(ind) = stack.pop()
+        {
+            for (other_ind, other) in words.iter().enumerate()
             {
-                WordDirection::Right => max_corner.0 = max_corner.0.max(word.position.x + word.value.chars().count() as isize),
-                WordDirection::Down => max_corner.1 = max_corner.1.max(word.position.y + word.value.chars().count() as isize), 
+                if !connected.contains(&other_ind) && do_words_cross(words[ind], other)
+                {
+                    connected.insert(other_ind);
+                    stack.push(other_ind);
+                }
             }
         }
-    
-        (max_corner.0 as usize, max_corner.1 as usize)
+
+        connected.len() == words.len()
+    }
+
+    /// Returns the [statistics](CrosswordStats) of the crossword
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},      //    |c a t|
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cow", id: 0},       //    |o    |
+    /// ]);                                                                                                        //    |w    |
+    ///                                                                                                            //     -----
+    /// let stats = cw.get_stats();
+    /// assert_eq!((stats.across, stats.down, stats.diagonal), (1, 1, 0));
+    /// assert_eq!(stats.letter_cells, 5);
+    /// assert_eq!(stats.get_intersection_count(), 1);
+    /// ```
+    pub fn get_stats(&self) -> CrosswordStats
+    {
+        let across = self.words.iter().filter(|w| w.direction.is_horizontal()).count();
+        let down = self.words.iter().filter(|w| w.direction.is_vertical()).count();
+
+        CrosswordStats
+        {
+            across,
+            down,
+            diagonal: self.words.len() - across - down,
+            size: self.get_size(),
+            letter_cells: self.get_cell_count(),
+            total_length: self.words.iter().map(|w| w.value.chars().count()).sum(),
+        }
     }
-    
+
 
     /// Returns a matrix of characters that represent the [crossword](Crossword)
     /// 
@@ -332,13 +1036,13 @@ impl<'a> Crossword<'a>
     /// ```
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
     /// # use crossword_generator::crossword::Crossword;                                         
-    /// let mut cw = Crossword::new(&[                                                                      //     ---------
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},   //    |h e l l o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},    //    |    o    |
-    /// ]);                                                                                                 //    |    c    |
-    ///                                                                                                     //    |    a    |
-    ///                                                                                                     //    |    l    |
-    ///                                                                                                     //     ---------
+    /// let mut cw = Crossword::new(&[                                                                             //     ---------
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},   //    |h e l l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    /// ]);                                                                                                        //    |    c    |
+    ///                                                                                                            //    |    a    |
+    ///                                                                                                            //    |    l    |
+    ///                                                                                                            //     ---------
     /// 
     /// 
     /// assert_eq!(cw.generate_char_table(), vec!
@@ -353,16 +1057,13 @@ impl<'a> Crossword<'a>
     pub fn generate_char_table(&self) ->Vec<Vec<char>>
     {
         let size = self.get_size();
+        let min_corner = self.get_min_corner();
         let mut table = vec![vec![' '; size.0]; size.1];
         for word in self.words.iter()
         {
-            for (index, char) in word.value.chars().enumerate()
+            for (position, char) in word.get_cells()
             {
-                match word.direction
-                {
-                    WordDirection::Right => table[word.position.y as usize][word.position.x as usize + index] = char,
-                    WordDirection::Down => table[word.position.y as usize + index][word.position.x as usize] = char,
-                }
+                table[(position.y - min_corner.y) as usize][(position.x - min_corner.x) as usize] = char;
             }
         }
     
@@ -376,13 +1077,13 @@ impl<'a> Crossword<'a>
     /// ```
     /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
     /// # use crossword_generator::crossword::Crossword;                                         
-    /// let mut cw = Crossword::new(&[                                                                      //     ---------
-    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},   //    |h e l l o|
-    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},    //    |    o    |
-    /// ]);                                                                                                 //    |    c    |
-    ///                                                                                                     //    |    a    |
-    ///                                                                                                     //    |    l    |
-    ///                                                                                                     //     ---------
+    /// let mut cw = Crossword::new(&[                                                                             //     ---------
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},   //    |h e l l o|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},    //    |    o    |
+    /// ]);                                                                                                        //    |    c    |
+    ///                                                                                                            //    |    a    |
+    ///                                                                                                            //    |    l    |
+    ///                                                                                                            //     ---------
     /// 
     /// 
     /// assert_eq!(cw.generate_string(), 
@@ -397,24 +1098,212 @@ impl<'a> Crossword<'a>
     /// ```
     pub fn generate_string(&self) -> String
     {
-        let table = self.generate_char_table();
-        let size = table[0].len() * 2 + 1;
-        let result: String = vec![vec!['-'; size], vec!['\n']].concat().into_iter().chain(table
-            .into_iter()
-            .map(|mut el| 
+        format_char_table(self.generate_char_table())
+    }
+
+    /// Reads a [crossword](Crossword) from its grid representation, the same as parsing a [CrosswordGrid]
+    ///
+    /// The returned grid owns the string values of the words, [get_crossword](CrosswordGrid::get_crossword) lends them to the crossword.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},      //    |    o|
+    /// ]);                                                                                                        //    |    y|
+    ///                                                                                                            //     -----
+    /// let grid = Crossword::from_grid_str(&cw.generate_string()).unwrap();
+    /// assert_eq!(grid.get_crossword(), cw);
+    /// ```
+    pub fn from_grid_str(grid: &str) -> Result<CrosswordGrid, GridParseError>
+    {
+        grid.parse()
+    }
+
+    /// Reads the cells of a boxed grid (the format of [generate_string](Crossword::generate_string)) or of a plain grid
+    ///
+    /// Returns the rows of the grid, [None] for an empty cell
+    fn parse_grid_cells(grid: &str) -> Result<Vec<Vec<Option<char>>>, GridParseError>
+    {
+        let lines: Vec<(usize, &str)> = grid.lines().enumerate().map(|(ind, line)| (ind + 1, line)).collect();
+        let first = lines.iter().position(|(_, line)| !line.trim().is_empty());
+        let last = lines.iter().rposition(|(_, line)| !line.trim().is_empty());
+        let lines = match (first, last)
+        {
+            (Some(first), Some(last)) => &lines[first..=last],
+            _ => return Err(GridParseError::Empty)
+        };
+
+        let is_border = |line: &str| !line.is_empty() && line.chars().all(|ch| ch == '-');
+
+        if !is_border(lines[0].1.trim_end())
+        {
+            // plain grid, one character per cell
+            return Ok(lines.iter()
+                .map(|(_, line)| line.chars().map(|ch| if ch == ' ' || ch == '.' || ch == '#' { None } else { Some(ch) }).collect())
+                .collect());
+        }
+
+        let border_width = lines[0].1.trim_end().chars().count();
+        let (last_line, last_border) = lines[lines.len() - 1];
+        if lines.len() < 2 || !is_border(last_border.trim_end()) || last_border.trim_end().chars().count() != border_width
+        {
+            return Err(GridParseError::MalformedBorder{ line: last_line });
+        }
+
+        let mut rows = vec![];
+        for (line_number, line) in lines[1..lines.len() - 1].iter()
+        {
+            let chars: Vec<char> = line.trim_end().chars().collect();
+            if chars.len() != border_width || chars.len() < 3 || chars[0] != '|' || chars[chars.len() - 1] != '|'
             {
-                el = el.into_iter().flat_map(|ch| [ch, ' ']).collect();
-                el.insert(0, '|');
-                el.pop();
-                el.push('|');
-                el.push('\n');
-                el
-            })
-            .flatten()).chain(vec![vec!['-'; size], vec!['\n']].concat().into_iter())
+                return Err(GridParseError::MalformedRow{ line: *line_number });
+            }
+
+            let inner = &chars[1..chars.len() - 1];
+            if inner.iter().skip(1).step_by(2).any(|ch| *ch != ' ')
+            {
+                return Err(GridParseError::MalformedRow{ line: *line_number });
+            }
+            rows.push(inner.iter().step_by(2).map(|ch| if *ch == ' ' { None } else { Some(*ch) }).collect());
+        }
+
+        Ok(rows)
+    }
+}
+
+/// A [crossword](Crossword) read from its grid representation with [from_grid_str](Crossword::from_grid_str) or [parse](str::parse), it owns the string values of its words and [lends](CrosswordGrid::get_crossword) them to a crossword
+///
+/// Two formats are accepted:
+/// - the boxed grid written by [generate_string](Crossword::generate_string), with cells separated by spaces
+/// - a plain grid without a border, with one character per cell, where ' ', '.' and '#' are empty cells
+///
+/// Every horizontal run of two or more letters becomes a [right](WordDirection::Right) [word](Word), and every vertical one a [down](WordDirection::Down) word.
+/// A letter that belongs to no such run becomes a one character right word. Copies of the same word get [ids](Word::id) in the order of their positions.
+///
+/// A grid does not keep where a word ends, so two words touching head by head are read as one word.
+/// Runs that touch a parallel run side by side can't be told apart from the two letter words across them, such grids are rejected with [GridParseError::SideBySideWords].
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+/// # use crossword_generator::crossword::{Crossword, CrosswordGrid};
+/// let grid: CrosswordGrid = "\
+/// -----------
+/// |h e l l o|
+/// |    o    |
+/// |    c    |
+/// |    a    |
+/// |    l    |
+/// -----------\n".parse().unwrap();
+///
+/// assert_eq!(grid.get_crossword(), Crossword::new(&[
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+/// ]));
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug)]
+pub struct CrosswordGrid
+{
+    words: Vec<(String, WordPosition, WordDirection)>
+}
+
+impl CrosswordGrid
+{
+    /// Returns the [crossword](Crossword) of the grid, it borrows the string values of its words from the grid
+    pub fn get_crossword(&self) -> Crossword<'_>
+    {
+        let words: Vec<Word<'_>> = self.words.iter()
+            .map(|(value, position, direction)| Word{ position: position.clone(), direction: direction.clone(), value: value.as_str(), id: 0 })
             .collect();
-    
-    
-        result
+
+        let mut cw = Crossword::new(&words);
+        cw.renumber_duplicates();
+        cw
+    }
+}
+
+/// A run of two or more letters of a grid, the range of its cells along its row or column
+struct GridRun
+{
+    direction: WordDirection,
+    line: usize,
+    range: std::ops::Range<usize>,
+    first_cell: (usize, usize)
+}
+
+impl std::str::FromStr for CrosswordGrid
+{
+    type Err = GridParseError;
+
+    fn from_str(grid: &str) -> Result<CrosswordGrid, GridParseError>
+    {
+        let rows = Crossword::parse_grid_cells(grid)?;
+        let height = rows.len();
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let cell = |x: usize, y: usize| rows.get(y).and_then(|row| row.get(x).copied()).flatten();
+
+        let mut found: Vec<(String, WordPosition, WordDirection)> = vec![];
+        let mut runs: Vec<GridRun> = vec![];
+        let mut covered = vec![vec![false; width]; height];
+
+        for (direction, outer, inner) in [(WordDirection::Right, height, width), (WordDirection::Down, width, height)]
+        {
+            for o in 0..outer
+            {
+                let mut i = 0;
+                while i < inner
+                {
+                    let at = |i: usize| if direction.is_horizontal() { (i, o) } else { (o, i) };
+                    let start = i;
+                    let mut value = String::new();
+                    while i < inner
+                    {
+                        let (x, y) = at(i);
+                        match cell(x, y)
+                        {
+                            Some(ch) => value.push(ch),
+                            None => break
+                        }
+                        i += 1;
+                    }
+                    if i - start >= 2
+                    {
+                        (start..i).map(at).for_each(|(x, y)| covered[y][x] = true);
+                        let (x, y) = at(start);
+                        if let Some(run) = runs.iter().find(|run| run.direction == direction && run.line + 1 == o && run.range.start < i && start < run.range.end)
+                        {
+                            return Err(GridParseError::SideBySideWords{ first: run.first_cell, second: (x, y) });
+                        }
+                        runs.push(GridRun { direction: direction.clone(), line: o, range: start..i, first_cell: (x, y) });
+                        found.push((value, WordPosition { x: x as isize, y: y as isize }, direction.clone()));
+                    }
+                    i += 1;
+                }
+            }
+        }
+
+        for (y, covered_row) in covered.iter().enumerate()
+        {
+            for (x, is_covered) in covered_row.iter().enumerate()
+            {
+                if let (Some(ch), false) = (cell(x, y), is_covered)
+                {
+                    found.push((ch.to_string(), WordPosition { x: x as isize, y: y as isize }, WordDirection::Right));
+                }
+            }
+        }
+
+        if found.is_empty()
+        {
+            return Err(GridParseError::Empty);
+        }
+
+        Ok(CrosswordGrid { words: found })
     }
 }
 
@@ -430,36 +1319,36 @@ mod tests {
     fn test_crossword_contains_crossword() {
         let cw = Crossword::new(
             &[
-                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
 
             ]);
 
         let mut containing_crossword_1 = Crossword::new(
             &[
-                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
 
             ]);
 
         let mut containing_crossword_2 = Crossword::new(
             &[
-                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 4, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 4, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
 
             ]);
 
         let mut containing_crossword_3 = Crossword::new(
             &[
-                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
 
             ]);
 
@@ -470,15 +1359,51 @@ mod tests {
         assert_eq!([cw.contains_crossword(&containing_crossword_1), cw.contains_crossword(&containing_crossword_2), cw.contains_crossword(&containing_crossword_3)], [true, true, false]);
     }
 
+    #[test]
+    fn test_crossword_size_constraints() {
+        let cw = Crossword::new(
+            &[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and", id: 0},
+            ]);
+
+        assert_eq!(cw.get_size(), (5, 5));
+        assert_eq!(cw.get_cell_count(), 13);
+
+        let cases = [
+            (CrosswordSizeConstraint::MinLength(5), true, true),
+            (CrosswordSizeConstraint::MinLength(6), false, true),
+            (CrosswordSizeConstraint::MinHeight(6), false, true),
+            (CrosswordSizeConstraint::ExactSize(5, 5), true, true),
+            (CrosswordSizeConstraint::ExactSize(6, 5), false, true),
+            (CrosswordSizeConstraint::ExactSize(5, 4), false, false),
+            (CrosswordSizeConstraint::MinAspectRatio(1, 1), true, true),
+            (CrosswordSizeConstraint::MinAspectRatio(3, 2), false, true),
+            (CrosswordSizeConstraint::MaxAspectRatio(2, 3), false, true),
+            (CrosswordSizeConstraint::MaxEmptyCellRatio(12, 25), true, true),
+            (CrosswordSizeConstraint::MaxEmptyCellRatio(2, 5), false, true),
+            (CrosswordSizeConstraint::MaxCells(13), true, true),
+            (CrosswordSizeConstraint::MaxCells(12), false, false),
+            (CrosswordSizeConstraint::MaxArea(24), false, false),
+        ];
+        for (constraint, valid, extendable) in cases
+        {
+            assert_eq!(constraint.is_crossword_valid(&cw), valid, "{:?}", constraint);
+            assert_eq!(constraint.can_be_extended(&cw), extendable, "{:?}", constraint);
+        }
+    }
+
     #[test]
     fn test_crossword_generate_string() {
         let cw = Crossword::new(
             &[
-                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
     
             ]);
 
@@ -498,11 +1423,11 @@ mod tests {
     fn test_crossword_normalize() {
         let mut cw = Crossword::new(
             &[
-                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
     
             ]);
         
@@ -510,11 +1435,11 @@ mod tests {
 
         let cw_normalized = Crossword::new(
             &[
-                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 4, y: 2 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 4, y: 2 }, direction: WordDirection::Down, value: "toy", id: 0},
 
             ]);
 
@@ -525,11 +1450,11 @@ mod tests {
     fn test_crossword_remove_word() {
         let mut cw = Crossword::new(
             &[
-                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and"},
-                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy"},
+                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
     
             ]);
         
@@ -537,10 +1462,10 @@ mod tests {
 
         let cw_word_removed = Crossword::new(
             &[
-                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat"},
-                Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and"},
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Down, value: "and", id: 0},
 
             ]);
 
@@ -551,21 +1476,21 @@ mod tests {
     fn test_crossword_calculate_possible_ways_to_add_word() {
         let cw = Crossword::new(
             &[
-                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello"},
-                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local"},
-                Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "tac"}
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "tac", id: 0}
             ]);
 
         let new_word = "hatlo";
 
         assert_eq!(cw.calculate_possible_ways_to_add_word(&new_word, &WordCompatibilitySettings::default()), vec![
-            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: new_word},
-            //Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Down, value: new_word.clone()},  |-
-            //Word{position: WordPosition { x: 1, y: 3 }, direction: WordDirection::Right, value: new_word.clone()}, ||
-            //Word{position: WordPosition { x: 3, y: -3 }, direction: WordDirection::Down, value: new_word.clone()}, ||
-            Word{position: WordPosition { x: -1, y: 4 }, direction: WordDirection::Right, value: new_word},
-            //Word{position: WordPosition { x: -2, y: 1 }, direction: WordDirection::Right, value: new_word.clone()},||
-            Word{position: WordPosition { x: 4, y: -4 }, direction: WordDirection::Down, value: new_word},
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: new_word, id: 0},
+            //Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Down, value: new_word.clone(), id: 0},  |-
+            //Word{position: WordPosition { x: 1, y: 3 }, direction: WordDirection::Right, value: new_word.clone(), id: 0}, ||
+            //Word{position: WordPosition { x: 3, y: -3 }, direction: WordDirection::Down, value: new_word.clone(), id: 0}, ||
+            Word{position: WordPosition { x: -1, y: 4 }, direction: WordDirection::Right, value: new_word, id: 0},
+            //Word{position: WordPosition { x: -2, y: 1 }, direction: WordDirection::Right, value: new_word.clone(), id: 0},||
+            Word{position: WordPosition { x: 4, y: -4 }, direction: WordDirection::Down, value: new_word, id: 0},
             ].into_iter().collect());
 
         // assert_eq!(cw.generate_string(), 
@@ -583,5 +1508,176 @@ mod tests {
         // ---------------------\n".to_owned())
     }
 
+    #[test]
+    fn test_crossword_grid() {
+        let cw = Crossword::new(
+            &[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 2, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 4, y: -3 }, direction: WordDirection::Down, value: "halo", id: 0},
+            ]);
+
+        let grid: CrosswordGrid = cw.generate_string().parse().unwrap();
+        assert_eq!(grid.get_crossword(), cw);
+
+        let grid = Crossword::from_grid_str("\n....h\n....a\n....l\nhello\n..o..\n..cat\n..a\n..l\n\n").unwrap();
+        assert_eq!(grid.get_crossword(), cw);
+
+        let grid: CrosswordGrid = "a".parse().unwrap();
+        assert_eq!(grid.get_crossword(), Crossword::new(&[Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "a", id: 0}]));
+    }
+
+    #[test]
+    fn test_crossword_grid_side_by_side() {
+        let error = "\
+-----------
+|h e l l o|
+|    o    |
+|    c a t|
+|    a n o|
+|    l d y|
+-----------".parse::<CrosswordGrid>().unwrap_err();
+        assert_eq!(error, GridParseError::SideBySideWords{ first: (2, 2), second: (2, 3) });
+        assert_eq!(error.to_string(), "the words starting at cells (2, 2) and (2, 3) lie side by side, so the words of the grid are ambiguous");
+
+        assert_eq!("ab\n.cd".parse::<CrosswordGrid>(), Err(GridParseError::SideBySideWords{ first: (0, 0), second: (1, 1) }));
+        assert!("ab\n..cd".parse::<CrosswordGrid>().is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_words() {
+        let mut cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+        ]);
+        cw.add_word(&Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "cat", id: 1});
+        cw.add_word(&Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cat", id: 1});
+        assert_eq!(cw.get_words().len(), 3);
+        assert_eq!(cw.find_word_with_id("cat", 1).unwrap().position, WordPosition { x: 0, y: 2 });
+
+        let mut swapped = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 1},
+        ]);
+        assert!(cw.contains_crossword(&swapped) && swapped.contains_crossword(&cw));
+        assert_ne!(cw, swapped);
+        swapped.renumber_duplicates();
+        assert_eq!(cw, swapped);
+
+        cw.remove_word_with_id("cat", 0);
+        assert_eq!(cw.get_words().iter().map(|w| (w.value, w.id)).collect::<Vec<_>>(), vec![("cat", 1), ("tot", 0)]);
+        assert_eq!(cw.find_word("cat").unwrap().position, WordPosition { x: 0, y: 2 });
+
+        let grid: CrosswordGrid = "cat\na\nt".parse().unwrap();
+        let cw = grid.get_crossword();
+        assert_eq!(cw.find_word_with_id("cat", 0).unwrap().direction, WordDirection::Right);
+        assert_eq!(cw.find_word_with_id("cat", 1).unwrap().direction, WordDirection::Down);
+    }
+
+    #[test]
+    fn test_try_edit() {
+        let settings = WordCompatibilitySettings::default();
+        let cat = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0};
+        let tot = Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0};
+        let mut cw = Crossword::new(&[cat.clone(), tot.clone()]);
+        let original = cw.clone();
+
+        let error = cw.try_add_word(&cat, &settings).unwrap_err();
+        assert_eq!(error, CrosswordError::DuplicateWord{ value: "cat".to_owned(), id: 0 });
+        assert_eq!(error.to_string(), "the word \"cat\" (id 0) is already in the crossword");
+        assert_eq!(cw.try_add_word(&Word{ id: 1, ..cat.clone() }, &settings), Err(CrosswordError::IncompatibleWord{ value: "cat".to_owned(), id: 1, other_value: "cat".to_owned(), other_id: 0 }));
+        assert_eq!(cw.try_add_word(&Word{position: WordPosition { x: 0, y: 1 }, direction: WordDirection::Right, value: "it", id: 0}, &settings),
+            Err(CrosswordError::IncompatibleWord{ value: "it".to_owned(), id: 0, other_value: "cat".to_owned(), other_id: 0 }));
+
+        assert_eq!(cw.try_move_word(&Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0}, &settings),
+            Err(CrosswordError::LetterConflict{ position: WordPosition { x: 0, y: 0 }, letter: 't', other_value: "cat".to_owned(), other_id: 0, other_letter: 'c' }));
+        assert_eq!(cw.try_move_word(&Word{ id: 1, ..tot.clone() }, &settings), Err(CrosswordError::WordNotFound{ value: "tot".to_owned(), id: 1 }));
+        assert_eq!(cw, original);
+
+        assert_eq!(cw.try_remove_word("tot", 1), Err(CrosswordError::WordNotFound{ value: "tot".to_owned(), id: 1 }));
+        assert_eq!(cw.try_remove_word("tot", 0), Ok(tot));
+        assert_eq!(cw, Crossword::new(&[cat]));
+    }
+
+    #[test]
+    fn test_crossword_grid_errors() {
+        assert_eq!(" \n\n".parse::<CrosswordGrid>(), Err(GridParseError::Empty));
+        assert_eq!(Crossword::from_grid_str("-----\n|   |\n-----"), Err(GridParseError::Empty));
+        assert_eq!("-----\n|c a|\n".parse::<CrosswordGrid>(), Err(GridParseError::MalformedBorder{ line: 2 }));
+        assert_eq!("-------\n|c a t|\n|a   |\n-------".parse::<CrosswordGrid>(), Err(GridParseError::MalformedRow{ line: 3 }));
+        assert_eq!("-------\n|c-a t|\n-------".parse::<CrosswordGrid>(), Err(GridParseError::MalformedRow{ line: 2 }));
+    }
 
+    #[test]
+    fn test_checked_cell_count() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 1 }, direction: WordDirection::Right, value: "xay", id: 0},
+            Word{position: WordPosition { x: 1, y: 0 }, direction: WordDirection::Down, value: "bac", id: 0},
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::DownRight, value: "dae", id: 0},
+        ]);
+        assert_eq!(cw.get_checked_cell_count(), 1);
+        assert_eq!(cw.get_crossing_count(cw.find_word("xay").unwrap()), 2);
+    }
+
+    #[test]
+    fn test_is_connected() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+            Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "art", id: 0},
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "cha", id: 0},
+        ]);
+        assert!(cw.is_connected());
+        assert!(Crossword::default().is_connected());
+
+        let mut cw = cw;
+        cw.remove_word("cha");
+        assert!(cw.is_connected());
+        cw.remove_word("tot");
+        assert!(!cw.is_connected());
+        assert!(cw.is_connected_by(|_, _| true));
+    }
+
+    #[test]
+    fn test_get_stats() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::DownRight, value: "cot", id: 0},
+        ]);
+        let stats = cw.get_stats();
+        assert_eq!(stats, CrosswordStats{ across: 1, down: 1, diagonal: 1, size: (3, 3), letter_cells: 6, total_length: 9 });
+        assert_eq!(stats.get_word_count(), 3);
+        assert_eq!(stats.get_intersection_count(), 3);
+        assert!((stats.get_density() - 600.0 / 9.0).abs() < 1e-9);
+        assert!((stats.get_average_word_length() - 3.0).abs() < 1e-9);
+
+        let empty = Crossword::default().get_stats();
+        assert_eq!(empty.get_density(), 0.0);
+        assert_eq!(empty.get_average_word_length(), 0.0);
+    }
+
+    #[test]
+    fn test_negative_coordinates() {
+        let cw = Crossword::new_absolute(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Down, value: "tot", id: 0},
+            Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "ho", id: 0},
+        ]);
+        assert_eq!(cw.get_min_corner(), WordPosition { x: -1, y: -2 });
+        assert_eq!(cw.get_size(), (4, 3));
+        assert_eq!(cw.get_cell_count(), 7);
+        assert_eq!(cw.get_stats().size, (4, 3));
+        assert_eq!(cw.get_cell_numbers(), BTreeMap::from([((3, 0), 1), ((0, 1), 2), ((1, 2), 3)]));
+        assert_eq!(cw.generate_string(),
+"\
+---------
+|      t|
+|h o   o|
+|  c a t|
+---------\n");
+        assert!(cw.generate_svg(&crate::svg::SvgSettings::default()).unwrap().contains("viewBox"));
+    }
 }
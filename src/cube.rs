@@ -0,0 +1,449 @@
+use std::collections::BTreeSet;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::WordCompatibilitySettings;
+use super::lattice::*;
+
+
+/// Represents the position of the first character of a [cube word](CubeWord) in a [cube crossword](CubeCrossword)
+///
+/// z is the number of the layer, x and y are the coordinates in the layer like in [WordPosition](super::word::WordPosition).
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct CubePosition
+{
+    pub x: isize,
+    pub y: isize,
+    pub z: isize
+}
+
+/// Represents the axis a [cube word](CubeWord) runs along, always towards bigger coordinates
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub enum CubeDirection
+{
+    /// Across a layer, like [Right](super::word::WordDirection::Right)
+    #[default]
+    X,
+    /// Down a layer, like [Down](super::word::WordDirection::Down)
+    Y,
+    /// Through the layers
+    Z
+}
+
+/// All three [cube directions](CubeDirection)
+pub const CUBE_DIRECTIONS: [CubeDirection; 3] = [CubeDirection::X, CubeDirection::Y, CubeDirection::Z];
+
+impl CubeDirection
+{
+    /// Returns the index of the axis: 0 for [X](CubeDirection::X), 1 for [Y](CubeDirection::Y) and 2 for [Z](CubeDirection::Z)
+    pub fn get_axis(&self) -> usize
+    {
+        match *self
+        {
+            CubeDirection::X => 0,
+            CubeDirection::Y => 1,
+            CubeDirection::Z => 2
+        }
+    }
+}
+
+/// Relation of the coordinates of two [cube words](CubeWord) on one axis
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum AxisRelation
+{
+    Overlap,
+    Adjacent,
+    Far
+}
+
+/// Represents a word in a [cube crossword](CubeCrossword)
+///
+/// Two cube words touch if their cells are neighbours: they touch by sides or heads if they are neighbours on one axis
+/// and by corners if they are only neighbours on two or three axes. Touching by sides along an axis that is not the
+/// direction of any of the words, for example two words in neighbouring layers, is treated as [side_by_side](WordCompatibilitySettings::side_by_side).
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct CubeWord<'a>
+{
+    pub position: CubePosition,
+    pub direction: CubeDirection,
+    pub value: &'a str
+}
+
+impl<'a> CubeWord<'a>
+{
+    /// Returns the position of the character with the given index
+    fn get_cell_position(&self, index: usize) -> CubePosition
+    {
+        let mut coordinates = [self.position.x, self.position.y, self.position.z];
+        coordinates[self.direction.get_axis()] += index as isize;
+        CubePosition { x: coordinates[0], y: coordinates[1], z: coordinates[2] }
+    }
+
+    /// Returns the positions of the first and the last character
+    pub fn get_corners(&self) -> (CubePosition, CubePosition)
+    {
+        (self.position.clone(), self.get_cell_position(self.value.chars().count().saturating_sub(1)))
+    }
+
+    /// Returns the first and the last coordinate of the [word](CubeWord) on every axis
+    fn get_ranges(&self) -> [(isize, isize); 3]
+    {
+        let (first, last) = self.get_corners();
+        [(first.x, last.x), (first.y, last.y), (first.z, last.z)]
+    }
+
+    fn get_axis_relations(&self, other: &CubeWord) -> [AxisRelation; 3]
+    {
+        let ranges = self.get_ranges();
+        let other_ranges = other.get_ranges();
+        let mut relations = [AxisRelation::Far; 3];
+        for axis in 0..3
+        {
+            let ((first, last), (other_first, other_last)) = (ranges[axis], other_ranges[axis]);
+            relations[axis] = if first <= other_last && other_first <= last
+            {
+                AxisRelation::Overlap
+            }
+            else if last + 1 == other_first || other_last + 1 == first
+            {
+                AxisRelation::Adjacent
+            }
+            else
+            {
+                AxisRelation::Far
+            };
+        }
+        relations
+    }
+
+    /// Returns the axis the [words](CubeWord) are neighbours on, if they touch by sides or heads
+    fn get_touching_axis(&self, other: &CubeWord) -> Option<usize>
+    {
+        let relations = self.get_axis_relations(other);
+        if relations.contains(&AxisRelation::Far) { return None; }
+        let adjacent: Vec<usize> = (0..3).filter(|axis| relations[*axis] == AxisRelation::Adjacent).collect();
+        (adjacent.len() == 1).then(|| adjacent[0])
+    }
+}
+
+/// The touching rules of cube words compare the ranges of their coordinates on the three axes
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::cube::{CubeDirection, CubePosition, CubeWord};
+/// # use crossword_generator::lattice::LatticeWord;
+/// let hello = CubeWord{ position: CubePosition{ x: 0, y: 0, z: 0 }, direction: CubeDirection::X, value: "hello" };
+///
+/// let ways = hello.calculate_possible_ways_to_add_word("yo");
+/// assert_eq!(ways.len(), 2);
+/// assert!(ways.contains(&CubeWord{ position: CubePosition{ x: 4, y: 0, z: -1 }, direction: CubeDirection::Z, value: "yo" }));
+/// ```
+impl<'a> LatticeWord<'a> for CubeWord<'a>
+{
+    type Position = CubePosition;
+    type Direction = CubeDirection;
+
+    fn new(position: CubePosition, direction: CubeDirection, value: &'a str) -> Self
+    {
+        CubeWord { position, direction, value }
+    }
+
+    fn get_direction(&self) -> &CubeDirection
+    {
+        &self.direction
+    }
+
+    fn get_value(&self) -> &'a str
+    {
+        self.value
+    }
+
+    fn get_cells(&self) -> Vec<(CubePosition, char)>
+    {
+        self.value.chars().enumerate().map(|(ind, ch)| (self.get_cell_position(ind), ch)).collect()
+    }
+
+    fn get_directions() -> Vec<CubeDirection>
+    {
+        CUBE_DIRECTIONS.to_vec()
+    }
+
+    /// [X](CubeDirection::X) and [Y](CubeDirection::Y), a crossword with a word along Z has a word crossing it along one of them
+    fn get_first_directions() -> Vec<CubeDirection>
+    {
+        vec![CubeDirection::X, CubeDirection::Y]
+    }
+
+    fn get_start_position(cell: &CubePosition, direction: &CubeDirection, index: usize) -> CubePosition
+    {
+        let mut coordinates = [cell.x, cell.y, cell.z];
+        coordinates[direction.get_axis()] -= index as isize;
+        CubePosition { x: coordinates[0], y: coordinates[1], z: coordinates[2] }
+    }
+
+    fn get_min_position(first: &CubePosition, second: &CubePosition) -> CubePosition
+    {
+        CubePosition { x: first.x.min(second.x), y: first.y.min(second.y), z: first.z.min(second.z) }
+    }
+
+    fn moved_to_origin(&self, origin: &CubePosition) -> Self
+    {
+        CubeWord { position: CubePosition { x: self.position.x - origin.x, y: self.position.y - origin.y, z: self.position.z - origin.z }, ..self.clone() }
+    }
+
+    fn intersects(&self, other: &CubeWord) -> bool
+    {
+        self.get_axis_relations(other).iter().all(|relation| *relation == AxisRelation::Overlap)
+    }
+
+    fn get_intersection_indices(&self, other: &CubeWord) -> Option<(usize, usize)>
+    {
+        if self.direction == other.direction || !self.intersects(other) { return None; }
+
+        let offset = |word: &CubeWord, crossing: &CubeWord|
+        {
+            let axis = word.direction.get_axis();
+            (crossing.get_ranges()[axis].0 - word.get_ranges()[axis].0) as usize
+        };
+        Some((offset(self, other), offset(other, self)))
+    }
+
+    /// The words are neighbours on two or three axes, the cells touch by an edge or a vertex of the cube
+    fn corners_touch(&self, other: &CubeWord) -> bool
+    {
+        let relations = self.get_axis_relations(other);
+        !relations.contains(&AxisRelation::Far) && relations.iter().filter(|relation| **relation == AxisRelation::Adjacent).count() >= 2
+    }
+
+    fn head_touches_head(&self, other: &CubeWord) -> bool
+    {
+        self.direction == other.direction && self.get_touching_axis(other) == Some(self.direction.get_axis())
+    }
+
+    /// Words in neighbouring layers touch side by side even if they are perpendicular
+    fn side_touches_side(&self, other: &CubeWord) -> bool
+    {
+        self.get_touching_axis(other).is_some_and(|axis| axis != self.direction.get_axis() && axis != other.direction.get_axis())
+    }
+
+    fn side_touches_head(&self, other: &CubeWord) -> bool
+    {
+        self.direction != other.direction &&
+        self.get_touching_axis(other).is_some_and(|axis| axis == self.direction.get_axis() || axis == other.direction.get_axis())
+    }
+}
+
+/// Represents a three-dimensional crossword: layers of grids stacked on each other, [words](CubeWord) run along X, Y or Z
+///
+/// After every change the crossword is [normalized](LatticeCrossword::normalize), so the first layer is z = 0 and every layer starts at x = 0 and y = 0.
+pub type CubeCrossword<'a> = LatticeCrossword<CubeWord<'a>>;
+
+impl<'a> CubeCrossword<'a>
+{
+    /// Returns the (x, y, z) size of the crossword, z is the number of layers
+    pub fn get_size(&self) -> (usize, usize, usize)
+    {
+        let mut size = [0; 3];
+        for word in self.get_words().iter()
+        {
+            for (axis, (_, last)) in word.get_ranges().into_iter().enumerate()
+            {
+                size[axis] = size[axis].max(last as usize + 1);
+            }
+        }
+        (size[0], size[1], size[2])
+    }
+
+    /// Returns the layers of the crossword from z = 0, every layer is a matrix of characters like [generate_char_table](super::crossword::Crossword::generate_char_table)
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::cube::{CubeCrossword, CubeDirection, CubePosition, CubeWord};
+    /// let cw = CubeCrossword::new(&[                                                                                  // z = 0      z = 1
+    ///     CubeWord{ position: CubePosition{ x: 0, y: 0, z: 0 }, direction: CubeDirection::X, value: "hey" },          // h e y      . . o
+    ///     CubeWord{ position: CubePosition{ x: 2, y: 0, z: 0 }, direction: CubeDirection::Z, value: "yo" },           //
+    /// ]);
+    ///
+    /// assert_eq!(cw.generate_layers(), vec![vec![vec!['h', 'e', 'y']], vec![vec![' ', ' ', 'o']]]);
+    /// ```
+    pub fn generate_layers(&self) -> Vec<Vec<Vec<char>>>
+    {
+        let (width, height, depth) = self.get_size();
+        let mut layers = vec![vec![vec![' '; width]; height]; depth];
+        for word in self.get_words().iter()
+        {
+            for (position, ch) in word.get_cells()
+            {
+                layers[position.z as usize][position.y as usize][position.x as usize] = ch;
+            }
+        }
+        layers
+    }
+
+    /// Returns a printable [String] with the boxed layers of the crossword, each after a "z = n" line
+    pub fn generate_string(&self) -> String
+    {
+        let mut result = String::new();
+        for (z, layer) in self.generate_layers().into_iter().enumerate()
+        {
+            let border = "-".repeat(layer.first().map_or(0, |row| row.len()) * 2 + 1);
+            result += &format!("z = {}\n{}\n", z, border);
+            for row in layer
+            {
+                result += &format!("|{}|\n", row.iter().map(|ch| ch.to_string()).collect::<Vec<_>>().join(" "));
+            }
+            result += &format!("{}\n", border);
+        }
+        result
+    }
+}
+
+/// Generator of [cube crosswords](CubeCrossword)
+///
+/// ## Fields
+///
+/// words -> set of strings, the words that will be used to generate cube crosswords, unlike [CrosswordGenerator::words](super::generator::CrosswordGenerator::words)
+/// a word can't be placed twice
+///
+/// word_compatibility_settings -> [WordCompatibilitySettings] applied to the cube words
+///
+/// max_size -> (x, y, z) size the crosswords have to fit in, None for no limit
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct CubeCrosswordGenerator
+{
+    pub words: BTreeSet<String>,
+    pub word_compatibility_settings: WordCompatibilitySettings,
+    pub max_size: Option<(usize, usize, usize)>
+}
+
+impl CubeCrosswordGenerator
+{
+    /// Generates all possible [cube crosswords](CubeCrossword) with all of the [words](CubeCrosswordGenerator::words)
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::cube::CubeCrosswordGenerator;
+    /// let generator = CubeCrosswordGenerator { words: ["hello", "yo"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+    ///
+    /// let crosswords = generator.generate_crosswords();
+    /// assert_eq!(crosswords.len(), 6);
+    /// assert!(crosswords.iter().any(|cw| cw.get_size() == (5, 1, 2)));
+    /// ```
+    pub fn generate_crosswords(&self) -> BTreeSet<CubeCrossword<'_>>
+    {
+        self.crossword_iter().collect()
+    }
+
+    /// Returns an iterator over all the [cube crosswords](CubeCrossword) with all of the [words](CubeCrosswordGenerator::words)
+    ///
+    /// The crosswords are generated lazily, partial crosswords that don't fit in [max_size](CubeCrosswordGenerator::max_size) are not extended.
+    pub fn crossword_iter(&self) -> CubeCrosswordIterator<'_>
+    {
+        let max_size = self.max_size;
+        LatticeCrosswordIterator::new(&self.words, &self.word_compatibility_settings, move |cw: &CubeCrossword| match max_size
+        {
+            Some((max_x, max_y, max_z)) =>
+            {
+                let (x, y, z) = cw.get_size();
+                x <= max_x && y <= max_y && z <= max_z
+            },
+            None => true
+        })
+    }
+}
+
+/// Iterator over [cube crosswords](CubeCrossword), returned by [crossword_iter](CubeCrosswordGenerator::crossword_iter)
+pub type CubeCrosswordIterator<'a> = LatticeCrosswordIterator<'a, CubeWord<'a>>;
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_cube_word_touching() {
+        let hello = CubeWord::new(CubePosition { x: 0, y: 0, z: 0 }, CubeDirection::X, "hello");
+
+        let crossing = CubeWord::new(CubePosition { x: 4, y: 0, z: -1 }, CubeDirection::Z, "yo");
+        assert!(hello.intersects(&crossing));
+        assert_eq!(hello.get_intersection_indices(&crossing), Some((4, 1)));
+        assert!(hello.is_compatible_with(&crossing, &WordCompatibilitySettings::default()));
+        assert!(!hello.is_compatible_with(&CubeWord::new(CubePosition { x: 3, y: 0, z: -1 }, CubeDirection::Z, "yo"), &WordCompatibilitySettings::default()));
+
+        let next_layer = CubeWord::new(CubePosition { x: 0, y: 0, z: 1 }, CubeDirection::X, "world");
+        assert!(hello.side_touches_side(&next_layer));
+        assert!(!hello.head_touches_head(&next_layer));
+
+        let perpendicular_next_layer = CubeWord::new(CubePosition { x: 2, y: -1, z: 1 }, CubeDirection::Y, "abc");
+        assert!(hello.side_touches_side(&perpendicular_next_layer));
+        assert!(!hello.side_touches_head(&perpendicular_next_layer));
+        assert!(!hello.intersects(&perpendicular_next_layer));
+
+        let head = CubeWord::new(CubePosition { x: 5, y: 0, z: 0 }, CubeDirection::X, "world");
+        assert!(hello.head_touches_head(&head));
+        assert!(!hello.side_touches_side(&head));
+
+        let side_head = CubeWord::new(CubePosition { x: 2, y: 0, z: 1 }, CubeDirection::Z, "abc");
+        assert!(hello.side_touches_head(&side_head));
+        assert!(side_head.side_touches_head(&hello));
+
+        let corner = CubeWord::new(CubePosition { x: 5, y: 1, z: 1 }, CubeDirection::Z, "abc");
+        assert!(hello.corners_touch(&corner));
+        assert!(!hello.side_touches_head(&corner));
+        assert!(hello.is_compatible_with(&corner, &WordCompatibilitySettings::default()));
+        assert!(!hello.is_compatible_with(&corner, &WordCompatibilitySettings { corner_by_corner: false, ..Default::default() }));
+
+        assert!(!hello.intersects(&CubeWord::new(CubePosition { x: 0, y: 0, z: 2 }, CubeDirection::X, "hello")));
+        assert!(hello.is_compatible_with(&CubeWord::new(CubePosition { x: 0, y: 0, z: 2 }, CubeDirection::X, "hello"), &WordCompatibilitySettings::default()));
+    }
+
+    #[test]
+    fn test_cube_crossword() {
+        let mut cw = CubeCrossword::new(&[
+            CubeWord::new(CubePosition { x: 0, y: 0, z: 0 }, CubeDirection::X, "cat"),
+            CubeWord::new(CubePosition { x: 0, y: 0, z: -2 }, CubeDirection::Z, "arc"),
+            CubeWord::new(CubePosition { x: 1, y: -2, z: 0 }, CubeDirection::Y, "zoa"),
+        ]);
+
+        assert_eq!(cw.get_size(), (3, 3, 3));
+        assert_eq!(cw.find_word("cat"), Some(&CubeWord::new(CubePosition { x: 0, y: 2, z: 2 }, CubeDirection::X, "cat")));
+        assert_eq!(cw.generate_layers()[2], vec![vec![' ', 'z', ' '], vec![' ', 'o', ' '], vec!['c', 'a', 't']]);
+        assert!(cw.generate_string().starts_with("z = 0\n-------\n|     |\n|     |\n|a    |\n-------\nz = 1\n"));
+
+        let json = serde_json::to_string(&cw).unwrap();
+        assert_eq!(serde_json::from_str::<CubeCrossword>(&json).unwrap(), cw);
+
+        cw.remove_word("zoa");
+        assert_eq!(cw.get_size(), (3, 1, 3));
+        cw.add_word(&CubeWord::new(CubePosition { x: 1, y: -2, z: 2 }, CubeDirection::Y, "zoa"));
+        assert_eq!(cw.get_size(), (3, 3, 3));
+    }
+
+    #[test]
+    fn test_cube_generator() {
+        let generator = CubeCrosswordGenerator { words: ["cat", "arc", "zoa"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let crosswords = generator.generate_crosswords();
+
+        assert!(crosswords.iter().any(|cw| cw.get_words().iter().map(|w| w.direction.clone()).collect::<BTreeSet<_>>().len() == 3));
+        for cw in crosswords.iter()
+        {
+            assert_eq!(cw.get_words().len(), 3);
+            assert!(cw.are_words_compatible(&generator.word_compatibility_settings));
+        }
+
+        assert_eq!(generator.crossword_iter().take(2).count(), 2);
+        assert_eq!(generator.crossword_iter().count(), crosswords.len());
+
+        let flat = CubeCrosswordGenerator { words: ["hello", "yo"].into_iter().map(|s| s.to_owned()).collect(), max_size: Some((5, 2, 1)), ..Default::default() };
+        assert_eq!(flat.generate_crosswords(), BTreeSet::from([CubeCrossword::new(&[
+            CubeWord::new(CubePosition { x: 0, y: 1, z: 0 }, CubeDirection::X, "hello"),
+            CubeWord::new(CubePosition { x: 4, y: 0, z: 0 }, CubeDirection::Y, "yo"),
+        ])]));
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+
+
+/// Represents an operation recorded by an [edit session](EditSession)
+///
+/// Operations keep everything needed to undo them, words are given as they were before the operation.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum EditOperation<'a>
+{
+    /// The [word](Word) was added
+    Add(#[serde(borrow)] Word<'a>),
+    /// The [word](Word) was removed
+    Remove(#[serde(borrow)] Word<'a>),
+    /// The [word](Word) was moved from one position and direction to another
+    Move
+    {
+        #[serde(borrow)]
+        from: Word<'a>,
+        #[serde(borrow)]
+        to: Word<'a>
+    },
+    /// The direction of the [word](Word) was [flipped](WordDirection::flipped), it stays on the same first cell
+    Flip(#[serde(borrow)] Word<'a>),
+    /// The clue stored under the [clue key](Word::get_clue_key) was changed, None means no clue
+    SetClue
+    {
+        key: String,
+        previous: Option<String>,
+        clue: Option<String>
+    }
+}
+
+/// Represents an editing session of a [crossword](Crossword) with undo and redo
+///
+/// Every operation is checked against the [word compatibility settings](WordCompatibilitySettings) of the session, an operation that fails leaves the session unchanged.
+/// The session works on an [absolute](Crossword::new_absolute) copy of the crossword, so the positions of words don't change when other words are edited,
+/// [into_crossword](EditSession::into_crossword) gives back a [normalized](Crossword::normalize) crossword if the original one was not absolute.
+///
+/// Clues are stored by the [clue key](Word::get_clue_key) of their word, and they are kept when the word is removed.
+/// The session, including its history, can be serialized and restored later.
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibilitySettings};
+/// # use crossword_generator::crossword::Crossword;
+/// # use crossword_generator::edit::EditSession;
+/// # use std::collections::BTreeMap;
+/// let cw = Crossword::new(&[                                                                                 //     -----
+///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+/// ]);                                                                                                        //     -----
+/// let mut session = EditSession::new(&cw, BTreeMap::new(), WordCompatibilitySettings::default());
+///
+/// session.add_word(&Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0}).unwrap();
+/// session.set_clue("toy", 0, Some("Plaything".to_owned())).unwrap();
+/// assert!(session.add_word(&Word{position: WordPosition { x: 0, y: 1 }, direction: WordDirection::Right, value: "it", id: 0}).is_err());
+/// assert_eq!(session.get_history().len(), 2);
+///
+/// session.undo();
+/// session.undo();
+/// assert_eq!(session.get_crossword().get_words().len(), 1);
+/// session.redo();
+/// assert_eq!(session.into_crossword().generate_string(),
+/// "\
+/// -------
+/// |c a t|
+/// |    o|
+/// |    y|
+/// -------\n");
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct EditSession<'a>
+{
+    #[serde(borrow)]
+    crossword: Crossword<'a>,
+    normalize: bool,
+    clues: BTreeMap<String, String>,
+    word_compatibility_settings: WordCompatibilitySettings,
+    #[serde(borrow)]
+    history: Vec<EditOperation<'a>>,
+    #[serde(borrow)]
+    undone: Vec<EditOperation<'a>>
+}
+
+impl<'a> EditSession<'a>
+{
+    /// Starts a session with the [crossword](Crossword) and its clues, stored by [clue keys](Word::get_clue_key)
+    pub fn new(crossword: &Crossword<'a>, clues: BTreeMap<String, String>, word_compatibility_settings: WordCompatibilitySettings) -> EditSession<'a>
+    {
+        let words: Vec<Word<'a>> = crossword.get_words().iter().cloned().collect();
+        EditSession
+        {
+            crossword: Crossword::new_absolute(&words),
+            normalize: !crossword.is_absolute(),
+            clues,
+            word_compatibility_settings,
+            history: vec![],
+            undone: vec![]
+        }
+    }
+
+    /// Returns the edited [crossword](Crossword), it is [absolute](Crossword::new_absolute) during the session
+    pub fn get_crossword(&self) -> &Crossword<'a>
+    {
+        &self.crossword
+    }
+
+    /// Ends the session and returns the [crossword](Crossword), [normalized](Crossword::normalize) unless the session was started with an absolute one
+    pub fn into_crossword(self) -> Crossword<'a>
+    {
+        match self.normalize
+        {
+            true => Crossword::new(&self.crossword.get_words().iter().cloned().collect::<Vec<Word<'a>>>()),
+            false => self.crossword
+        }
+    }
+
+    /// Returns the clues by [clue keys](Word::get_clue_key)
+    pub fn get_clues(&self) -> &BTreeMap<String, String>
+    {
+        &self.clues
+    }
+
+    /// Returns the [word compatibility settings](WordCompatibilitySettings) the operations are checked against
+    pub fn get_word_compatibility_settings(&self) -> &WordCompatibilitySettings
+    {
+        &self.word_compatibility_settings
+    }
+
+    /// Returns the operations that can be undone, the last one is undone first
+    pub fn get_history(&self) -> &[EditOperation<'a>]
+    {
+        &self.history
+    }
+
+    /// Returns the undone operations that can be redone, the last one is redone first
+    pub fn get_undone(&self) -> &[EditOperation<'a>]
+    {
+        &self.undone
+    }
+
+    /// Returns the word of the crossword with the string value and [id](Word::id), or an error if it is not found
+    fn get_word(&self, value: &str, id: usize) -> Result<Word<'a>, CrosswordError>
+    {
+        self.crossword.find_word_with_id(value, id).cloned().ok_or_else(|| CrosswordError::WordNotFound{ value: value.to_owned(), id })
+    }
+
+    /// Records a successful operation, the undone operations can't be redone after it
+    fn record(&mut self, operation: EditOperation<'a>)
+    {
+        self.history.push(operation);
+        self.undone.clear();
+    }
+
+    /// Adds the [word](Word) to the crossword, see [try_add_word](Crossword::try_add_word)
+    pub fn add_word(&mut self, word: &Word<'a>) -> Result<(), CrosswordError>
+    {
+        self.crossword.try_add_word(word, &self.word_compatibility_settings)?;
+        self.record(EditOperation::Add(word.clone()));
+        Ok(())
+    }
+
+    /// Removes the word with the string value and [id](Word::id) from the crossword, see [try_remove_word](Crossword::try_remove_word)
+    pub fn remove_word(&mut self, value: &str, id: usize) -> Result<(), CrosswordError>
+    {
+        let removed = self.crossword.try_remove_word(value, id)?;
+        self.record(EditOperation::Remove(removed));
+        Ok(())
+    }
+
+    /// Moves the word with the string value and [id](Word::id) of the given [word](Word) to its position and direction, see [try_move_word](Crossword::try_move_word)
+    pub fn move_word(&mut self, word: &Word<'a>) -> Result<(), CrosswordError>
+    {
+        let from = self.crossword.try_move_word(word, &self.word_compatibility_settings)?;
+        self.record(EditOperation::Move{ from, to: word.clone() });
+        Ok(())
+    }
+
+    /// [Flips](WordDirection::flipped) the direction of the word with the string value and [id](Word::id), for example from [Right](WordDirection::Right) to [Down](WordDirection::Down), the word keeps its first cell
+    pub fn flip_word(&mut self, value: &str, id: usize) -> Result<(), CrosswordError>
+    {
+        let word = self.get_word(value, id)?;
+        let from = self.crossword.try_move_word(&Word{ direction: word.direction.flipped(), ..word }, &self.word_compatibility_settings)?;
+        self.record(EditOperation::Flip(from));
+        Ok(())
+    }
+
+    /// Sets the clue of the word with the string value and [id](Word::id), None removes the clue
+    pub fn set_clue(&mut self, value: &str, id: usize, clue: Option<String>) -> Result<(), CrosswordError>
+    {
+        let key = self.get_word(value, id)?.get_clue_key();
+        let previous = self.set_clue_by_key(&key, clue.clone());
+        self.record(EditOperation::SetClue{ key, previous, clue });
+        Ok(())
+    }
+
+    /// Sets the clue stored under the key and returns the previous one
+    fn set_clue_by_key(&mut self, key: &str, clue: Option<String>) -> Option<String>
+    {
+        match clue
+        {
+            Some(clue) => self.clues.insert(key.to_owned(), clue),
+            None => self.clues.remove(key)
+        }
+    }
+
+    /// Moves a word of the crossword to the position and direction of the target, without checks
+    fn place_word(&mut self, target: &Word<'a>)
+    {
+        self.crossword.remove_word_with_id(target.value, target.id);
+        self.crossword.add_word(target);
+    }
+
+    /// Applies the operation to the session (or its inverse if `forward` is false) without checks, the session is in the state the operation was recorded in
+    fn apply(&mut self, operation: &EditOperation<'a>, forward: bool)
+    {
+        match (operation, forward)
+        {
+            (EditOperation::Add(word), true) | (EditOperation::Remove(word), false) => self.crossword.add_word(word),
+            (EditOperation::Add(word), false) | (EditOperation::Remove(word), true) => self.crossword.remove_word_with_id(word.value, word.id),
+            (EditOperation::Move{ to, .. }, true) => self.place_word(to),
+            (EditOperation::Move{ from, .. }, false) | (EditOperation::Flip(from), false) => self.place_word(from),
+            (EditOperation::Flip(from), true) => self.place_word(&Word{ direction: from.direction.flipped(), ..from.clone() }),
+            (EditOperation::SetClue{ key, clue, .. }, true) => { self.set_clue_by_key(key, clue.clone()); },
+            (EditOperation::SetClue{ key, previous, .. }, false) => { self.set_clue_by_key(key, previous.clone()); }
+        }
+    }
+
+    /// Undoes the last operation and returns it, or None if there is nothing to undo
+    pub fn undo(&mut self) -> Option<EditOperation<'a>>
+    {
+        let operation = self.history.pop()?;
+        self.apply(&operation, false);
+        self.undone.push(operation.clone());
+        Some(operation)
+    }
+
+    /// Redoes the last undone operation and returns it, or None if there is nothing to redo
+    pub fn redo(&mut self) -> Option<EditOperation<'a>>
+    {
+        let operation = self.undone.pop()?;
+        self.apply(&operation, true);
+        self.history.push(operation.clone());
+        Some(operation)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_edit_session() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+        ]);
+        let clues = BTreeMap::from([("cat".to_owned(), "Meows".to_owned())]);
+        let mut session = EditSession::new(&cw, clues, WordCompatibilitySettings::default());
+
+        assert_eq!(session.flip_word("tot", 0), Err(CrosswordError::IncompatibleWord{ value: "tot".to_owned(), id: 0, other_value: "cat".to_owned(), other_id: 0 }));
+        session.move_word(&Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Down, value: "tot", id: 0}).unwrap();
+        session.add_word(&Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Right, value: "to", id: 0}).unwrap();
+        session.remove_word("to", 0).unwrap();
+        session.flip_word("tot", 0).unwrap();
+        session.set_clue("tot", 0, Some("Small child".to_owned())).unwrap();
+        session.set_clue("cat", 0, None).unwrap();
+        assert_eq!(session.set_clue("dog", 0, None), Err(CrosswordError::WordNotFound{ value: "dog".to_owned(), id: 0 }));
+        assert_eq!(session.get_history().len(), 6);
+        assert_eq!(session.get_clues(), &BTreeMap::from([("tot".to_owned(), "Small child".to_owned())]));
+        assert_eq!(session.get_crossword().find_word("tot").unwrap(), &Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Right, value: "tot", id: 0});
+
+        let json = serde_json::to_string(&session).unwrap();
+        let mut restored: EditSession = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, session);
+
+        while restored.undo().is_some() {}
+        assert_eq!(restored.get_undone().len(), 6);
+        assert_eq!(restored.get_clues(), &BTreeMap::from([("cat".to_owned(), "Meows".to_owned())]));
+        assert_eq!(restored.clone().into_crossword(), cw);
+
+        restored.redo();
+        restored.redo();
+        assert_eq!(restored.get_crossword().get_words().len(), 3);
+        restored.flip_word("to", 0).unwrap_err();
+        restored.remove_word("to", 0).unwrap();
+        assert!(restored.get_undone().is_empty());
+        assert_eq!(restored.redo(), None);
+        assert_eq!(restored.into_crossword().generate_string(),
+"\
+-------
+|    t|
+|    o|
+|c a t|
+-------\n");
+    }
+    #[test]
+    fn test_flip_diagonal_word() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::DownRight, value: "cat", id: 0},
+        ]);
+        let mut session = EditSession::new(&cw, BTreeMap::new(), WordCompatibilitySettings::default());
+
+        session.flip_word("cat", 0).unwrap();
+        assert_eq!(session.get_history().len(), 1);
+        assert_eq!(session.get_crossword().find_word("cat").unwrap().direction, WordDirection::UpRight);
+
+        session.flip_word("cat", 0).unwrap();
+        assert_eq!(session.get_crossword().find_word("cat").unwrap().direction, WordDirection::DownRight);
+
+        session.undo();
+        session.undo();
+        session.redo();
+        assert_eq!(session.get_crossword().find_word("cat").unwrap().direction, WordDirection::UpRight);
+        assert!(ALL_DIRECTIONS.iter().all(|direction| direction.flipped() != *direction && direction.flipped().flipped() == *direction));
+    }
+
+    #[test]
+    fn test_render_during_session() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+        ]);
+        let mut session = EditSession::new(&cw, BTreeMap::new(), WordCompatibilitySettings::default());
+
+        session.move_word(&Word{position: WordPosition { x: 2, y: -2 }, direction: WordDirection::Down, value: "tot", id: 0}).unwrap();
+        session.add_word(&Word{position: WordPosition { x: 1, y: -2 }, direction: WordDirection::Right, value: "at", id: 0}).unwrap();
+        assert_eq!(session.get_crossword().generate_string(),
+"\
+-------
+|  a t|
+|    o|
+|c a t|
+-------\n");
+    }
+}
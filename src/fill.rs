@@ -0,0 +1,606 @@
+use std::collections::BTreeSet;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+use super::index::*;
+
+
+/// Represents a cell of a [grid template](GridTemplate)
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub enum TemplateCell
+{
+    /// A cell that has to be filled with a letter
+    #[default]
+    Open,
+    /// A black square, no letter can be placed in it
+    Block,
+    /// A cell with a letter that is already given
+    Letter(char)
+}
+
+/// Represents an error that occurred while reading a [grid template](GridTemplate) with [from_ascii](GridTemplate::from_ascii)
+///
+/// Line numbers start from 1.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum TemplateParseError
+{
+    /// The template has no cells
+    Empty,
+    /// A row has a different number of cells than the first row
+    RaggedRow{ line: usize },
+    /// A character that is neither '#', '.' nor a letter
+    UnknownCharacter{ line: usize, ch: char },
+}
+
+impl std::fmt::Display for TemplateParseError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            TemplateParseError::Empty => write!(f, "the template has no cells"),
+            TemplateParseError::RaggedRow{ line } => write!(f, "line {}: the row has a different width than the first row", line),
+            TemplateParseError::UnknownCharacter{ line, ch } => write!(f, "line {}: unknown character '{}', expected '#' for a block, '.' for an open cell or a letter", line, ch),
+        }
+    }
+}
+
+impl std::error::Error for TemplateParseError {}
+
+/// Represents a place for a [word](Word) in a [grid template](GridTemplate): a horizontal or vertical run of two or more non block cells
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct Slot
+{
+    pub position: WordPosition,
+    pub direction: WordDirection,
+    pub length: usize
+}
+
+impl Slot
+{
+    /// Returns the cell coordinates (x, y) of the character with the given index
+    fn cell(&self, index: usize) -> (usize, usize)
+    {
+        let (dx, dy) = self.direction.get_step();
+        ((self.position.x + dx * index as isize) as usize, (self.position.y + dy * index as isize) as usize)
+    }
+}
+
+/// Represents a crossword grid with a given block pattern, that can be [filled](GridTemplate::fill) with words from a [dictionary](Dictionary)
+///
+/// ## Example
+///
+/// ```text
+/// c . .      c -> given letter
+/// . # .      . -> open cell
+/// . . .      # -> block
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct GridTemplate
+{
+    cells: Vec<Vec<TemplateCell>>
+}
+
+impl GridTemplate
+{
+    /// Creates a template of the given size with all cells [open](TemplateCell::Open)
+    pub fn new(width: usize, height: usize) -> GridTemplate
+    {
+        GridTemplate { cells: vec![vec![TemplateCell::Open; width]; height] }
+    }
+
+    /// Reads a template from ASCII art, one line per row, '#' is a [block](TemplateCell::Block), '.' is an [open](TemplateCell::Open) cell, and a letter is a [given letter](TemplateCell::Letter) (it is lowercased)
+    ///
+    /// Leading and trailing empty lines are ignored, spaces between cells are allowed.
+    pub fn from_ascii(template: &str) -> Result<GridTemplate, TemplateParseError>
+    {
+        let mut cells: Vec<Vec<TemplateCell>> = vec![];
+        for (ind, line) in template.lines().enumerate()
+        {
+            if line.trim().is_empty() { continue; }
+
+            let mut row = vec![];
+            for ch in line.chars().filter(|ch| !ch.is_whitespace())
+            {
+                row.push(match ch
+                {
+                    '#' => TemplateCell::Block,
+                    '.' => TemplateCell::Open,
+                    ch if ch.is_alphabetic() => TemplateCell::Letter(ch.to_lowercase().next().unwrap_or(ch)),
+                    ch => return Err(TemplateParseError::UnknownCharacter{ line: ind + 1, ch })
+                });
+            }
+
+            if cells.first().is_some_and(|first| first.len() != row.len())
+            {
+                return Err(TemplateParseError::RaggedRow{ line: ind + 1 });
+            }
+            cells.push(row);
+        }
+
+        if cells.is_empty() { return Err(TemplateParseError::Empty); }
+
+        Ok(GridTemplate { cells })
+    }
+
+    /// Returns the size (width, height) of the template
+    pub fn get_size(&self) -> (usize, usize)
+    {
+        (self.cells.first().map_or(0, |row| row.len()), self.cells.len())
+    }
+
+    /// Returns the cell at (x, y), or None if it is outside of the template
+    pub fn get_cell(&self, x: usize, y: usize) -> Option<TemplateCell>
+    {
+        self.cells.get(y).and_then(|row| row.get(x)).copied()
+    }
+
+    /// Sets the cell at (x, y), does nothing if it is outside of the template
+    pub fn set_cell(&mut self, x: usize, y: usize, cell: TemplateCell)
+    {
+        if let Some(c) = self.cells.get_mut(y).and_then(|row| row.get_mut(x))
+        {
+            *c = cell;
+        }
+    }
+
+    /// Returns a matrix of characters that represent the template, '#' for blocks, ' ' for open cells and the letter for given letters
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::fill::GridTemplate;
+    /// let template = GridTemplate::from_ascii("c..\n.#.\n...").unwrap();
+    ///
+    /// assert_eq!(template.generate_char_table(), vec!
+    /// [
+    ///     vec!['c', ' ', ' '],
+    ///     vec![' ', '#', ' '],
+    ///     vec![' ', ' ', ' '],
+    /// ]);
+    /// ```
+    pub fn generate_char_table(&self) -> Vec<Vec<char>>
+    {
+        self.cells.iter()
+            .map(|row| row.iter().map(|cell| match cell
+            {
+                TemplateCell::Open => ' ',
+                TemplateCell::Block => '#',
+                TemplateCell::Letter(ch) => *ch
+            }).collect())
+            .collect()
+    }
+
+    /// Returns all [slots](Slot) of the template, first the [right](WordDirection::Right) ones row by row, then the [down](WordDirection::Down) ones column by column
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::fill::{GridTemplate, Slot};
+    /// # use crossword_generator::word::{WordPosition, WordDirection};
+    /// let template = GridTemplate::from_ascii("\
+    /// ..#
+    /// ...").unwrap();
+    ///
+    /// assert_eq!(template.get_slots(), vec![
+    ///     Slot{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, length: 2 },
+    ///     Slot{ position: WordPosition{ x: 0, y: 1 }, direction: WordDirection::Right, length: 3 },
+    ///     Slot{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Down, length: 2 },
+    ///     Slot{ position: WordPosition{ x: 1, y: 0 }, direction: WordDirection::Down, length: 2 },
+    /// ]);
+    /// ```
+    pub fn get_slots(&self) -> Vec<Slot>
+    {
+        let (width, height) = self.get_size();
+        let mut slots = vec![];
+
+        for (direction, outer, inner) in [(WordDirection::Right, height, width), (WordDirection::Down, width, height)]
+        {
+            for o in 0..outer
+            {
+                let at = |i: usize| if direction.is_horizontal() { (i, o) } else { (o, i) };
+                let mut start = 0;
+                for i in 0..=inner
+                {
+                    let (x, y) = at(i);
+                    if i < inner && self.cells[y][x] != TemplateCell::Block { continue; }
+
+                    if i - start >= 2
+                    {
+                        let (x, y) = at(start);
+                        slots.push(Slot{ position: WordPosition{ x: x as isize, y: y as isize }, direction: direction.clone(), length: i - start });
+                    }
+                    start = i + 1;
+                }
+            }
+        }
+
+        slots
+    }
+}
+
+/// Represents a word of a [dictionary](Dictionary) with its weight, words with bigger weights are tried first while [filling](GridTemplate::fill)
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct DictionaryWord
+{
+    pub value: String,
+    pub weight: u32
+}
+
+/// Represents a weighted word list used to [fill](GridTemplate::fill) [grid templates](GridTemplate)
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct Dictionary
+{
+    pub words: Vec<DictionaryWord>
+}
+
+impl Dictionary
+{
+    /// Weight of the words that have no weight in [from_word_list](Dictionary::from_word_list)
+    pub const DEFAULT_WEIGHT: u32 = 50;
+
+    /// Reads a dictionary from a word list with one word per line, optionally followed by ';' and its weight ("hello;60")
+    ///
+    /// Words are lowercased, empty lines, lines starting with '#' and lines with an unparsable weight are skipped.
+    pub fn from_word_list(list: &str) -> Dictionary
+    {
+        let words = list.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| match line.split_once(';')
+            {
+                Some((word, weight)) => weight.trim().parse().ok().map(|weight| (word.trim(), weight)),
+                None => Some((line, Dictionary::DEFAULT_WEIGHT))
+            })
+            .map(|(word, weight)| DictionaryWord{ value: word.to_lowercase(), weight })
+            .collect();
+
+        Dictionary { words }
+    }
+}
+
+/// Represents settings for [filling](GridTemplate::fill) a [grid template](GridTemplate)
+///
+/// ## Fields
+///
+/// min_weight -> words of the [dictionary](Dictionary) with smaller weights are not used
+///
+/// max_steps -> maximum number of tried word placements before giving up with [FillError::StepLimitReached], 0 means no limit
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct FillSettings
+{
+    pub min_weight: u32,
+    pub max_steps: usize
+}
+
+/// Represents an error of [filling](GridTemplate::fill) a [grid template](GridTemplate)
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum FillError
+{
+    /// No word of the dictionary fits in the slot, even without other words placed
+    NoCandidates(Slot),
+    /// All possibilities were tried, the template can't be filled with the dictionary
+    NoSolution,
+    /// The search was stopped after [max_steps](FillSettings::max_steps) word placements
+    StepLimitReached
+}
+
+impl std::fmt::Display for FillError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            FillError::NoCandidates(slot) => write!(f, "no word of the dictionary fits the slot of length {} at ({}, {}) going {:?}", slot.length, slot.position.x, slot.position.y, slot.direction),
+            FillError::NoSolution => write!(f, "the template can't be filled with the words of the dictionary"),
+            FillError::StepLimitReached => write!(f, "the step limit was reached before a fill was found"),
+        }
+    }
+}
+
+impl std::error::Error for FillError {}
+
+/// State of the backtracking search of [GridTemplate::fill]
+struct FillSearch<'d>
+{
+    settings: &'d FillSettings,
+    slots: Vec<Slot>,
+    /// characters of every dictionary word
+    words: Vec<Vec<char>>,
+    /// for every slot, (index in slot, other slot, index in other slot) of every crossing
+    crossings: Vec<Vec<(usize, usize, usize)>>,
+    /// letters currently in the grid
+    letters: Vec<Vec<Option<char>>>,
+    /// candidate word indices of every slot, ordered by weight
+    domains: Vec<Vec<usize>>,
+    assigned: Vec<Option<usize>>,
+    used: BTreeSet<usize>,
+    steps: usize
+}
+
+impl<'d> FillSearch<'d>
+{
+    fn search(&mut self) -> Result<bool, FillError>
+    {
+        let slot = match (0..self.slots.len())
+            .filter(|s| self.assigned[*s].is_none())
+            .min_by_key(|s| self.domains[*s].iter().filter(|w| !self.used.contains(*w)).count())
+        {
+            Some(slot) => slot,
+            None => return Ok(true)
+        };
+
+        let candidates: Vec<usize> = self.domains[slot].iter().copied().filter(|w| !self.used.contains(w)).collect();
+        for word in candidates
+        {
+            self.steps += 1;
+            if self.settings.max_steps != 0 && self.steps > self.settings.max_steps
+            {
+                return Err(FillError::StepLimitReached);
+            }
+
+            let mut new_cells = vec![];
+            for index in 0..self.slots[slot].length
+            {
+                let (x, y) = self.slots[slot].cell(index);
+                if self.letters[y][x].is_none()
+                {
+                    self.letters[y][x] = Some(self.words[word][index]);
+                    new_cells.push(index);
+                }
+            }
+            self.assigned[slot] = Some(word);
+            self.used.insert(word);
+
+            let mut saved_domains = vec![];
+            let mut consistent = true;
+            for (index, other, other_index) in self.crossings[slot].clone()
+            {
+                if self.assigned[other].is_some() || !new_cells.contains(&index) { continue; }
+
+                let letter = self.words[word][index];
+                let filtered: Vec<usize> = self.domains[other].iter().copied().filter(|w| self.words[*w][other_index] == letter).collect();
+                let empty = filtered.iter().all(|w| self.used.contains(w));
+                saved_domains.push((other, std::mem::replace(&mut self.domains[other], filtered)));
+                if empty
+                {
+                    consistent = false;
+                    break;
+                }
+            }
+
+            if consistent && self.search()?
+            {
+                return Ok(true);
+            }
+
+            for (other, domain) in saved_domains.into_iter().rev()
+            {
+                self.domains[other] = domain;
+            }
+            self.used.remove(&word);
+            self.assigned[slot] = None;
+            for index in new_cells
+            {
+                let (x, y) = self.slots[slot].cell(index);
+                self.letters[y][x] = None;
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl GridTemplate
+{
+    /// Fills all [slots](GridTemplate::get_slots) of the template with different words of the [dictionary](Dictionary), so that crossing words share letters
+    ///
+    /// The search assigns the slot with the fewest remaining candidates first, tries its candidates from the heaviest to the lightest,
+    /// and after every placement removes the candidates of crossing slots that don't match the new letters (backtracking when a slot runs out of candidates).
+    ///
+    /// Returns a [crossword](Crossword) whose [words](Word) borrow their values from the dictionary, so all rendering functions of crosswords can be used on it.
+    /// Note that the crossword is [normalized](Crossword::normalize), so block only rows and columns on the top and left of the template are not part of it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::fill::{GridTemplate, Dictionary, FillSettings};
+    /// let template = GridTemplate::from_ascii("\
+    /// .a.
+    /// .#.
+    /// ...").unwrap();
+    /// let dictionary = Dictionary::from_word_list("cat\ncow\ntoe\nwoe\nbee");
+    ///
+    /// let cw = template.fill(&dictionary, &FillSettings::default()).unwrap();
+    ///
+    /// assert_eq!(cw.generate_string(),
+    /// "\
+    /// -------
+    /// |c a t|
+    /// |o   o|
+    /// |w o e|
+    /// -------\n");
+    /// ```
+    pub fn fill<'a>(&self, dictionary: &'a Dictionary, settings: &FillSettings) -> Result<Crossword<'a>, FillError>
+    {
+        let (width, height) = self.get_size();
+        let slots = self.get_slots();
+
+        let words: Vec<Vec<char>> = dictionary.words.iter().map(|w| w.value.chars().collect()).collect();
+        let index = WordIndex::new(dictionary.words.iter().map(|w| w.value.as_str()));
+
+        let mut cell_slots = vec![vec![vec![]; width]; height];
+        for (slot_ind, slot) in slots.iter().enumerate()
+        {
+            for index in 0..slot.length
+            {
+                let (x, y) = slot.cell(index);
+                cell_slots[y][x].push((slot_ind, index));
+            }
+        }
+
+        let crossings: Vec<Vec<(usize, usize, usize)>> = slots.iter().enumerate()
+            .map(|(slot_ind, slot)| (0..slot.length)
+                .flat_map(|index|
+                {
+                    let (x, y) = slot.cell(index);
+                    cell_slots[y][x].iter().filter(|(other, _)| *other != slot_ind).map(move |(other, other_index)| (index, *other, *other_index)).collect::<Vec<_>>()
+                })
+                .collect())
+            .collect();
+
+        let letters: Vec<Vec<Option<char>>> = self.cells.iter()
+            .map(|row| row.iter().map(|cell| match cell { TemplateCell::Letter(ch) => Some(*ch), _ => None }).collect())
+            .collect();
+
+        let mut domains = vec![];
+        for slot in slots.iter()
+        {
+            let pattern: String = (0..slot.length)
+                .map(|i|
+                {
+                    let (x, y) = slot.cell(i);
+                    letters[y][x].unwrap_or(WILDCARD)
+                })
+                .collect();
+            let mut domain: Vec<usize> = index.get_matching_ids(&pattern).into_iter()
+                .filter(|w| dictionary.words[*w].weight >= settings.min_weight)
+                .collect();
+            domain.sort_by_key(|w| std::cmp::Reverse(dictionary.words[*w].weight));
+
+            if domain.is_empty()
+            {
+                return Err(FillError::NoCandidates(slot.clone()));
+            }
+            domains.push(domain);
+        }
+
+        let mut search = FillSearch
+        {
+            settings,
+            assigned: vec![None; slots.len()],
+            slots,
+            words,
+            crossings,
+            letters,
+            domains,
+            used: BTreeSet::new(),
+            steps: 0
+        };
+
+        if !search.search()?
+        {
+            return Err(FillError::NoSolution);
+        }
+
+        let placed: Vec<Word<'a>> = search.slots.iter().zip(search.assigned.iter())
+            .map(|(slot, word)| Word
+            {
+                position: slot.position.clone(),
+                direction: slot.direction.clone(),
+                value: dictionary.words[word.expect("all slots are assigned after a successful search")].value.as_str(),
+                id: 0
+            })
+            .collect();
+
+        Ok(Crossword::new(&placed))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_grid_template_from_ascii() {
+        let template = GridTemplate::from_ascii("\n. . #\nA . .\n\n").unwrap();
+        assert_eq!(template.get_size(), (3, 2));
+        assert_eq!(template.get_cell(2, 0), Some(TemplateCell::Block));
+        assert_eq!(template.get_cell(0, 1), Some(TemplateCell::Letter('a')));
+        assert_eq!(template.get_cell(3, 0), None);
+
+        assert_eq!(GridTemplate::from_ascii("..\n...\n"), Err(TemplateParseError::RaggedRow{ line: 2 }));
+        assert_eq!(GridTemplate::from_ascii("..\n.?\n"), Err(TemplateParseError::UnknownCharacter{ line: 2, ch: '?' }));
+        assert_eq!(GridTemplate::from_ascii("\n\n"), Err(TemplateParseError::Empty));
+    }
+
+    #[test]
+    fn test_grid_template_get_slots() {
+        let template = GridTemplate::from_ascii("\
+...#.
+.#...
+.....").unwrap();
+
+        assert_eq!(template.get_slots().iter().map(|s| (s.position.x, s.position.y, s.direction.clone(), s.length)).collect::<Vec<_>>(), vec![
+            (0, 0, WordDirection::Right, 3),
+            (2, 1, WordDirection::Right, 3),
+            (0, 2, WordDirection::Right, 5),
+            (0, 0, WordDirection::Down, 3),
+            (2, 0, WordDirection::Down, 3),
+            (3, 1, WordDirection::Down, 2),
+            (4, 0, WordDirection::Down, 3),
+        ]);
+    }
+
+    #[test]
+    fn test_dictionary_from_word_list() {
+        let dictionary = Dictionary::from_word_list("Hello;60\n# comment\n\nworld\nbad;x\n");
+        assert_eq!(dictionary.words, vec![
+            DictionaryWord{ value: "hello".to_owned(), weight: 60 },
+            DictionaryWord{ value: "world".to_owned(), weight: Dictionary::DEFAULT_WEIGHT },
+        ]);
+    }
+
+    #[test]
+    fn test_grid_template_fill() {
+        let template = GridTemplate::from_ascii("\
+....
+.##.
+....").unwrap();
+        let dictionary = Dictionary::from_word_list("\
+trap;90
+tart;80
+toes;70
+newt
+ten
+pit
+sat");
+
+        let cw = template.fill(&dictionary, &FillSettings::default()).unwrap();
+        let table = cw.generate_char_table();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[1][1..3], [' ', ' ']);
+
+        let values: BTreeSet<&str> = cw.get_words().iter().map(|w| w.value).collect();
+        assert_eq!(values.len(), 4);
+        for word in cw.get_words().iter()
+        {
+            let letters: String = word.get_cells().into_iter()
+                .map(|(position, _)| table[position.y as usize][position.x as usize])
+                .collect();
+            assert_eq!(letters, word.value);
+        }
+
+        assert_eq!(values, BTreeSet::from(["trap", "ten", "pit", "newt"]));
+    }
+
+    #[test]
+    fn test_grid_template_fill_errors() {
+        let template = GridTemplate::from_ascii("...\n.#.\n...").unwrap();
+        let dictionary = Dictionary::from_word_list("cat\ncow\ntoe\nwoe");
+
+        assert_eq!(GridTemplate::from_ascii("....").unwrap().fill(&dictionary, &FillSettings::default()),
+            Err(FillError::NoCandidates(Slot{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, length: 4 })));
+        assert_eq!(GridTemplate::from_ascii("...\n.#.\n..x").unwrap().fill(&dictionary, &FillSettings::default()), Err(FillError::NoCandidates(Slot{ position: WordPosition{ x: 0, y: 2 }, direction: WordDirection::Right, length: 3 })));
+        assert_eq!(template.fill(&dictionary, &FillSettings { min_weight: 51, max_steps: 0 }), Err(FillError::NoCandidates(Slot{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, length: 3 })));
+        assert_eq!(template.fill(&Dictionary::from_word_list("cat\ncow\ntoe\nwow"), &FillSettings::default()), Err(FillError::NoSolution));
+        assert_eq!(template.fill(&dictionary, &FillSettings { min_weight: 0, max_steps: 1 }), Err(FillError::StepLimitReached));
+        assert!(template.fill(&dictionary, &FillSettings::default()).is_ok());
+    }
+}
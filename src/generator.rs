@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter;
 use serde::{Serialize, Deserialize};
 
@@ -13,30 +13,141 @@ use corosensei::stack::DefaultStack;
 
 use super::word::*;
 use super::crossword::*;
+use super::board::*;
 
 
 /// Represents settings needed for [crossword](Crossword) [generation](CrosswordGenerator)
-#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub struct CrosswordGeneratorSettings
 {
     pub word_compatibility_settings: WordCompatibilitySettings,
-    pub crossword_settings: CrosswordSettings
+    pub crossword_settings: CrosswordSettings,
+    /// If set, it is used instead of [word_compatibility_settings](CrosswordGeneratorSettings::word_compatibility_settings), it can't be serialized
+    #[serde(skip)]
+    pub custom_word_compatibility: Option<CustomWordCompatibility>,
+    /// If set, words are placed only on free cells of the [board](Board) and the generated crosswords are [absolute](Crossword::new_absolute)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<Board>,
+    /// Directions the words can be placed in, [Right](WordDirection::Right) and [Down](WordDirection::Down) by default
+    #[serde(default = "default_directions")]
+    pub directions: Vec<WordDirection>
+}
+
+fn default_directions() -> Vec<WordDirection>
+{
+    DEFAULT_DIRECTIONS.to_vec()
+}
+
+impl Default for CrosswordGeneratorSettings
+{
+    fn default() -> Self
+    {
+        CrosswordGeneratorSettings
+        {
+            word_compatibility_settings: WordCompatibilitySettings::default(),
+            crossword_settings: CrosswordSettings::default(),
+            custom_word_compatibility: None,
+            board: None,
+            directions: default_directions()
+        }
+    }
+}
+
+impl CrosswordGeneratorSettings
+{
+    /// Returns the [word compatibility](WordCompatibility) used for generation, the [custom one](CrosswordGeneratorSettings::custom_word_compatibility) if set
+    pub fn get_word_compatibility(&self) -> &dyn WordCompatibility
+    {
+        match &self.custom_word_compatibility
+        {
+            Some(custom) => custom.0.as_ref(),
+            None => &self.word_compatibility_settings
+        }
+    }
+
+    /// Checks if two [words](Word) are compatible with the [word compatibility](CrosswordGeneratorSettings::get_word_compatibility), on the [board](CrosswordGeneratorSettings::board) if there is one
+    pub fn are_words_compatible(&self, first: &Word, second: &Word) -> bool
+    {
+        match &self.board
+        {
+            Some(board) => board.are_words_compatible(first, second, self.get_word_compatibility()),
+            None => self.get_word_compatibility().are_words_compatible(first, second)
+        }
+    }
+
+    /// Returns the pairs of [words](Word) of the [crossword](Crossword) that are not [compatible](CrosswordGeneratorSettings::are_words_compatible)
+    pub fn get_incompatible_words<'b, 'a>(&self, cw: &'b Crossword<'a>) -> Vec<(&'b Word<'a>, &'b Word<'a>)>
+    {
+        let words: Vec<&Word<'a>> = cw.get_words().iter().collect();
+        words.iter().enumerate()
+            .flat_map(|(ind, first)| words[ind + 1..].iter().map(move |second| (*first, *second)))
+            .filter(|(first, second)| !self.are_words_compatible(first, second))
+            .collect()
+    }
+
+    /// Checks if the [crossword](Crossword) is [connected](Crossword::is_connected), on a [board](CrosswordGeneratorSettings::board) that wraps also across its edges
+    pub fn is_crossword_connected(&self, cw: &Crossword) -> bool
+    {
+        match &self.board
+        {
+            Some(board) => cw.is_connected_by(|first, second| board.do_words_cross(first, second)),
+            None => cw.is_connected()
+        }
+    }
+
+    /// Returns all possible ways to add a word into the [crossword](Crossword), on the [board](CrosswordGeneratorSettings::board) if there is one
+    ///
+    /// Ways that break the [word pair constraints](CrosswordSettings::word_pair_constraints) are left out.
+    pub fn calculate_possible_ways_to_add_word<'a>(&self, cw: &Crossword<'a>, word: &'a str) -> BTreeSet<Word<'a>>
+    {
+        let ways = match &self.board
+        {
+            Some(board) => board.calculate_possible_ways_to_add_word(cw, word, self.get_word_compatibility(), &self.directions),
+            None => cw.calculate_possible_ways_to_add_word_in_directions(word, self.get_word_compatibility(), &self.directions)
+        };
+
+        ways.into_iter().filter(|step| self.crossword_settings.is_step_allowed(cw, step)).collect()
+    }
+
+    /// Returns the crossword the generation starts from, [absolute](Crossword::new_absolute) if there is a [board](CrosswordGeneratorSettings::board)
+    fn get_initial_crossword<'a>(&self) -> Crossword<'a>
+    {
+        match self.board
+        {
+            Some(_) => Crossword::new_absolute(&[]),
+            None => Crossword::default()
+        }
+    }
 }
 
 /// Class for a generator
 /// 
 /// ## Fields
 /// 
-/// words -> set of strings, the words that will be used to generate [crosswords](Crossword)
+/// words -> list of strings, the words that will be used to generate [crosswords](Crossword), a word that is in the list several times is placed that many times,
+/// its n-th copy in the list gets the [id](Word::id) n - 1 in every generated crossword. The copies are interchangeable, so every layout is generated once,
+/// with the copies numbered in the order they were placed
 /// 
 /// settings -> [CrosswordGeneratorSettings]
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 pub struct CrosswordGenerator
 {
-    pub words: BTreeSet<String>,
+    pub words: Vec<String>,
     pub settings: CrosswordGeneratorSettings,
 }
 
+/// Returns the remaining entry with the lowest id of every word, the copies of a word are placed in the order of their ids, so swapping two copies doesn't give a new crossword
+fn get_next_entries<'a>(remained_words: &BTreeSet<(&'a str, usize)>) -> Vec<(&'a str, usize)>
+{
+    remained_words.iter().filter(|(value, id)| *id == 0 || !remained_words.contains(&(*value, id - 1))).cloned().collect()
+}
+
+/// Returns the string values of the remaining entries, a word with several copies left is in the list once per copy
+fn get_remained_values<'a>(remained_words: &BTreeSet<(&'a str, usize)>) -> Vec<&'a str>
+{
+    remained_words.iter().map(|(value, _)| *value).collect()
+}
+
 impl CrosswordGenerator
 {
     /// Generates all possible [crosswords](Crossword) constructed with [words](CrosswordGenerator::words) and the [settings](CrosswordGenerator::settings) taken into account
@@ -47,10 +158,24 @@ impl CrosswordGenerator
         self.crossword_iter().collect::<BTreeSet<Crossword>>()
     }
 
+    /// Returns the entries of [words](CrosswordGenerator::words) as pairs of a string value and an [id](Word::id), copies of a word are numbered from 0
+    fn get_entries(&self) -> BTreeSet<(&str, usize)>
+    {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        self.words.iter()
+            .map(|word|
+            {
+                let count = counts.entry(word.as_str()).or_default();
+                *count += 1;
+                (word.as_str(), *count - 1)
+            })
+            .collect()
+    }
+
     #[cfg(feature = "rec-iter")]
-    fn crossword_iter_rec_impl<'a>(&self, yielder: &Yielder<(), Crossword<'a>>, current_crossword: &mut Crossword<'a>, remained_words: &BTreeSet<&'a str>, full_created_crossword_bases: &mut BTreeSet<Crossword<'a>>)
+    fn crossword_iter_rec_impl<'a>(&self, yielder: &Yielder<(), Crossword<'a>>, current_crossword: &mut Crossword<'a>, remained_words: &BTreeSet<(&'a str, usize)>, full_created_crossword_bases: &mut BTreeSet<Crossword<'a>>)
     {
-        if !self.settings.crossword_settings.is_crossword_valid(&current_crossword) 
+        if !self.settings.crossword_settings.can_be_extended_with(current_crossword, &get_remained_values(remained_words))
         {
             return; 
         }
@@ -62,16 +187,19 @@ impl CrosswordGenerator
         
         if remained_words.is_empty()
         {
-            yielder.suspend(current_crossword.clone());
+            if self.settings.crossword_settings.is_crossword_valid(current_crossword)
+            {
+                yielder.suspend(current_crossword.clone());
+            }
             return;
         }
-        for current_word in remained_words.iter()
+        for (current_word, id) in get_next_entries(remained_words)
         {
             let mut new_remained_words = remained_words.clone();
-            new_remained_words.remove(current_word);
-            for step in current_crossword.calculate_possible_ways_to_add_word(current_word, &self.settings.word_compatibility_settings).iter()
+            new_remained_words.remove(&(current_word, id));
+            for step in self.settings.calculate_possible_ways_to_add_word(current_crossword, current_word).into_iter().map(|w| Word{ id, ..w })
             {
-                current_crossword.add_word(step);
+                current_crossword.add_word(&step);
 
                 self.crossword_iter_rec_impl(yielder, current_crossword, &new_remained_words, full_created_crossword_bases);
 
@@ -80,7 +208,7 @@ impl CrosswordGenerator
                 
                 full_created_crossword_bases.insert(current_crossword.clone());
 
-                current_crossword.remove_word(&step.value);
+                current_crossword.remove_word_with_id(step.value, step.id);
             }
         }
     }
@@ -92,8 +220,8 @@ impl CrosswordGenerator
         {
             generating_coroutine: ScopedCoroutine::new(|yielder, _|
             {
-                let mut crossword = Crossword::new(&[]);
-                let words = self.words.iter().map(|s| s.as_str()).collect::<BTreeSet<&str>>();
+                let mut crossword = self.settings.get_initial_crossword();
+                let words = self.get_entries();
 
                 let mut full_created_crossword_bases = BTreeSet::new();
 
@@ -111,13 +239,13 @@ impl CrosswordGenerator
         CrosswordIterator
         {
             settings: self.settings.clone(),
-            current_crossword: Crossword::default(),
+            current_crossword: self.settings.get_initial_crossword(),
             full_created_crossword_bases: BTreeSet::new(),
             frame_stack: vec!
             [
                 Frame
                 {
-                    remained_words: self.words.iter().map(|s| s.as_str()).collect(),
+                    remained_words: self.get_entries(),
                     ..Frame::new()
                 }
             ],
@@ -129,10 +257,10 @@ impl CrosswordGenerator
 
 struct Frame<'a>
 {
-    remained_words: BTreeSet<&'a str>,
-    new_remained_words: BTreeSet<&'a str>,
-    current_word_iterator: Box<dyn Iterator<Item = &'a str> + 'a>,
-    current_word: Option<&'a str>,
+    remained_words: BTreeSet<(&'a str, usize)>,
+    new_remained_words: BTreeSet<(&'a str, usize)>,
+    current_word_iterator: Box<dyn Iterator<Item = (&'a str, usize)> + 'a>,
+    current_word: Option<(&'a str, usize)>,
     current_step_iterator: Box<dyn Iterator<Item = Word<'a>> + 'a>,
     current_step: Option<Word<'a>>,
 }
@@ -185,7 +313,7 @@ impl<'a> Iterator for CrosswordIterator<'a>
         if !self.started
         {
             self.started = true;
-            self.current_frame().current_word_iterator = Box::new(self.current_frame().remained_words.clone().into_iter());
+            self.current_frame().current_word_iterator = Box::new(get_next_entries(&self.current_frame().remained_words).into_iter());
         }
         else
         {
@@ -196,8 +324,8 @@ impl<'a> Iterator for CrosswordIterator<'a>
             
             self.full_created_crossword_bases.insert(self.current_crossword.clone());
 
-            let step_to_remove = self.current_frame().current_step.as_ref().unwrap().value;
-            self.current_crossword.remove_word(step_to_remove);
+            let step_to_remove = self.current_frame().current_step.clone().unwrap();
+            self.current_crossword.remove_word_with_id(step_to_remove.value, step_to_remove.id);
 
         }
 
@@ -216,10 +344,10 @@ impl<'a> Iterator for CrosswordIterator<'a>
                 }
                 self.current_frame().new_remained_words = self.current_frame().remained_words.clone();
                 let word_to_remove = self.current_frame().current_word.unwrap();
-                self.current_frame().new_remained_words.remove(word_to_remove);
+                self.current_frame().new_remained_words.remove(&word_to_remove);
                 
-                let curr_word = self.current_frame().current_word.unwrap();
-                self.current_frame().current_step_iterator = Box::new(self.current_crossword.calculate_possible_ways_to_add_word(curr_word, &self.settings.word_compatibility_settings).into_iter());
+                let (curr_word, id) = self.current_frame().current_word.unwrap();
+                self.current_frame().current_step_iterator = Box::new(self.settings.calculate_possible_ways_to_add_word(&self.current_crossword, curr_word).into_iter().map(move |w| Word{ id, ..w }));
                 self.current_frame().current_step = self.current_frame().current_step_iterator.next();
             };
             
@@ -238,8 +366,8 @@ impl<'a> Iterator for CrosswordIterator<'a>
                     
                     self.full_created_crossword_bases.insert(self.current_crossword.clone());
         
-                    let step_to_remove = self.current_frame().current_step.as_ref().unwrap().value;
-                    self.current_crossword.remove_word(step_to_remove);
+                    let step_to_remove = self.current_frame().current_step.clone().unwrap();
+                    self.current_crossword.remove_word_with_id(step_to_remove.value, step_to_remove.id);
         
                     self.current_frame().current_step = self.current_frame().current_step_iterator.next();
                     continue;    
@@ -256,16 +384,18 @@ impl<'a> Iterator for CrosswordIterator<'a>
                 ..Frame::new()
             });
 
-            if !self.settings.crossword_settings.is_crossword_valid(&self.current_crossword) { continue; }
+            if !self.settings.crossword_settings.can_be_extended_with(&self.current_crossword, &get_remained_values(&self.frame_stack.last().expect("Frame stack must have at least one frame in it.").remained_words)) { continue; }
 
             if self.full_created_crossword_bases.iter().any(|cw| self.current_crossword.contains_crossword(cw)) { continue; }
 
             if !self.current_frame().remained_words.is_empty() 
             {
-                self.current_frame().current_word_iterator = Box::new(self.current_frame().remained_words.clone().into_iter());
+                self.current_frame().current_word_iterator = Box::new(get_next_entries(&self.current_frame().remained_words).into_iter());
                 continue; 
             }
 
+            if !self.settings.crossword_settings.is_crossword_valid(&self.current_crossword) { continue; }
+
             return Some(self.current_crossword.clone());
         }
     }
@@ -298,6 +428,8 @@ mod tests {
     
 
     use super::*;
+    use crate::constraint::*;
+    use itertools::Itertools;
 
     #[cfg(feature = "rec-iter")]
     #[test]
@@ -318,4 +450,257 @@ mod tests {
         generator.words = vec!["a", "accb", "b"].into_iter().map(|s| s.to_lowercase()).collect();
         assert_eq!(generator.crossword_iter().count(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_size_constraints_pruning()
+    {
+        let generator = CrosswordGenerator { words: ["hello", "local", "cat"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let all = generator.generate_crosswords();
+
+        for constraints in [
+            vec![CrosswordSizeConstraint::MaxLength(5), CrosswordSizeConstraint::MinHeight(6)],
+            vec![CrosswordSizeConstraint::ExactSize(5, 5)],
+            vec![CrosswordSizeConstraint::MaxCells(11), CrosswordSizeConstraint::MinAspectRatio(6, 5)],
+        ]
+        {
+            let mut constrained = generator.clone();
+            constrained.settings.crossword_settings.size_constraints = constraints;
+            let expected: BTreeSet<Crossword> = all.iter().filter(|cw| constrained.settings.crossword_settings.is_crossword_valid(cw)).cloned().collect();
+            assert!(!expected.is_empty());
+            assert_eq!(constrained.generate_crosswords(), expected);
+        }
+    }
+
+    #[test]
+    fn test_combined_constraints()
+    {
+        let generator = CrosswordGenerator { words: ["hello", "local", "cat"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let all = generator.generate_crosswords();
+
+        let mut constrained = generator.clone();
+        constrained.settings.crossword_settings.constraints = vec![CombinedConstraint::Or(vec![
+            CombinedConstraint::Size(CrosswordSizeConstraint::MinLength(6)),
+            CombinedConstraint::Size(CrosswordSizeConstraint::MinHeight(6)),
+        ])];
+        let expected: BTreeSet<Crossword> = all.iter().filter(|cw| cw.get_size() != (5, 5)).cloned().collect();
+        assert_eq!(expected.len(), 6);
+        assert_eq!(constrained.generate_crosswords(), expected);
+    }
+
+    #[test]
+    fn test_crossing_constraints()
+    {
+        for words in [["cat", "cow", "tie", "woe"], ["hello", "local", "halo", "ah"]]
+        {
+            let generator = CrosswordGenerator { words: words.into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+            let all = generator.generate_crosswords();
+
+            let mut constrained = generator.clone();
+            constrained.settings.crossword_settings.constraints = vec![CombinedConstraint::Crossing(CrossingConstraint::MinCrossingsPerWord(2))];
+            let expected: BTreeSet<Crossword> = all.iter().filter(|cw| cw.get_words().iter().all(|w| cw.get_crossing_count(w) >= 2)).cloned().collect();
+            assert_eq!(constrained.generate_crosswords(), expected);
+
+            constrained.settings.crossword_settings.constraints = vec![CombinedConstraint::Crossing(CrossingConstraint::MinCheckedCellRatio(1, 2))];
+            let expected: BTreeSet<Crossword> = all.iter().filter(|cw| cw.get_checked_cell_count() * 2 >= cw.get_cell_count()).cloned().collect();
+            assert_eq!(constrained.generate_crosswords(), expected);
+        }
+    }
+
+    #[test]
+    fn test_word_pair_constraints()
+    {
+        let generator = CrosswordGenerator { words: ["hello", "local", "halo", "ah"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let all = generator.generate_crosswords();
+
+        for constraint in [
+            WordPairConstraint::MustCross("halo".to_owned(), "hello".to_owned()),
+            WordPairConstraint::MustNotCross("halo".to_owned(), "ah".to_owned()),
+            WordPairConstraint::MustBeParallel("hello".to_owned(), "ah".to_owned()),
+            WordPairConstraint::MaxDistance("local".to_owned(), "ah".to_owned(), 1),
+        ]
+        {
+            let mut constrained = generator.clone();
+            constrained.settings.crossword_settings.word_pair_constraints = vec![constraint.clone()];
+
+            let expected: BTreeSet<Crossword> = all.iter().filter(|cw| constraint.is_crossword_valid(cw)).cloned().collect();
+            assert!(!expected.is_empty() && expected.len() < all.len());
+            assert_eq!(constrained.generate_crosswords(), expected);
+        }
+    }
+
+    #[test]
+    fn test_directions()
+    {
+        let generator = CrosswordGenerator { words: ["hello", "yo"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let classic = generator.generate_crosswords();
+
+        let mut all_directions = generator.clone();
+        all_directions.settings.directions = vec![WordDirection::Right, WordDirection::Down, WordDirection::Left, WordDirection::Up];
+        let all = all_directions.generate_crosswords();
+        assert!(classic.is_subset(&all));
+        assert_eq!(all.len(), 8);
+        assert!(all.contains(&Crossword::new(&[
+            Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Left, value: "hello", id: 0},
+            Word{position: WordPosition { x: 0, y: 1 }, direction: WordDirection::Up, value: "yo", id: 0},
+        ])));
+
+        let mut left_down = generator.clone();
+        left_down.settings.directions = vec![WordDirection::Left, WordDirection::Down];
+        for cw in left_down.generate_crosswords()
+        {
+            assert!(cw.get_words().iter().all(|w| w.direction == WordDirection::Left || w.direction == WordDirection::Down));
+        }
+    }
+
+    #[test]
+    fn test_diagonal_directions()
+    {
+        let generator = CrosswordGenerator { words: ["hello", "yo"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let classic = generator.generate_crosswords();
+
+        let mut with_diagonal = generator.clone();
+        with_diagonal.settings.directions = vec![WordDirection::Right, WordDirection::Down, WordDirection::DownRight];
+        let diagonal = with_diagonal.generate_crosswords();
+        assert!(classic.is_subset(&diagonal));
+        assert!(diagonal.contains(&Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 1 }, direction: WordDirection::Right, value: "hello", id: 0},
+            Word{position: WordPosition { x: 3, y: 0 }, direction: WordDirection::DownRight, value: "yo", id: 0},
+        ])));
+        for cw in diagonal
+        {
+            assert!(cw.get_words().iter().all(|w| with_diagonal.settings.directions.contains(&w.direction)));
+        }
+    }
+
+    struct NoFirstLetterCrossings;
+
+    impl WordCompatibility for NoFirstLetterCrossings
+    {
+        fn are_words_compatible(&self, first: &Word, second: &Word) -> bool
+        {
+            first.get_intersection_indices(second).is_none_or(|(i, j)| i != 0 && j != 0) && WordCompatibilitySettings::default().are_words_compatible(first, second)
+        }
+    }
+
+    #[test]
+    fn test_custom_word_compatibility()
+    {
+        let mut generator = CrosswordGenerator { words: ["hello", "halo", "local"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let all = generator.generate_crosswords().len();
+
+        generator.settings.custom_word_compatibility = Some(CustomWordCompatibility::new(NoFirstLetterCrossings));
+        let crosswords = generator.generate_crosswords();
+        assert!(!crosswords.is_empty() && crosswords.len() < all);
+        for cw in crosswords.iter()
+        {
+            for (first, second) in cw.get_words().iter().tuple_combinations()
+            {
+                assert!(NoFirstLetterCrossings.are_words_compatible(first, second));
+            }
+        }
+    }
+
+    #[test]
+    fn test_board()
+    {
+        let mut generator = CrosswordGenerator { words: ["hello", "lot"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        generator.settings.board = Some(Board::from_ascii(".....\n.#...\n....."));
+
+        assert_eq!(generator.generate_crosswords(), BTreeSet::from([
+            Crossword::new_absolute(&[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "lot", id: 0},
+            ]),
+            Crossword::new_absolute(&[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 3, y: 0 }, direction: WordDirection::Down, value: "lot", id: 0},
+            ]),
+        ]));
+    }
+
+    #[test]
+    fn test_torus_board()
+    {
+        let mut generator = CrosswordGenerator { words: ["hello", "lot"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let board = Board::new_torus(6, 4);
+        generator.settings.board = Some(board.clone());
+
+        let crosswords = generator.generate_crosswords();
+        assert_eq!(crosswords.len(), 6 * 4 * 3);
+        let wrapped = Crossword::new_absolute(&[
+            Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+            Word{position: WordPosition { x: 2, y: 3 }, direction: WordDirection::Down, value: "lot", id: 0},
+        ]);
+        assert!(crosswords.contains(&wrapped));
+        assert_eq!(board.generate_char_table(&wrapped), vec![
+            vec!['l', 'l', 'o', ' ', 'h', 'e'],
+            vec![' ', ' ', 't', ' ', ' ', ' '],
+            vec![' ', ' ', ' ', ' ', ' ', ' '],
+            vec![' ', ' ', 'l', ' ', ' ', ' '],
+        ]);
+
+        generator.settings.board = Some(Board::new_torus(5, 4));
+        assert!(generator.generate_crosswords().is_empty());
+    }
+
+    #[test]
+    fn test_validation_helpers()
+    {
+        let hello = Word{position: WordPosition { x: 4, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0};
+        let lot = Word{position: WordPosition { x: 2, y: 3 }, direction: WordDirection::Down, value: "lot", id: 0};
+        let cw = Crossword::new_absolute(&[hello.clone(), lot.clone()]);
+
+        let mut settings = CrosswordGeneratorSettings::default();
+        assert!(!settings.is_crossword_connected(&cw));
+        assert!(settings.get_incompatible_words(&cw).is_empty());
+
+        settings.board = Some(Board::new_torus(6, 4));
+        assert!(settings.is_crossword_connected(&cw));
+        assert!(settings.get_incompatible_words(&cw).is_empty());
+
+        let side = Word{position: WordPosition { x: 4, y: 1 }, direction: WordDirection::Right, value: "lot", id: 1};
+        let cw = Crossword::new_absolute(&[hello.clone(), side.clone()]);
+        assert_eq!(settings.get_incompatible_words(&cw), vec![(&hello, &side)]);
+    }
+
+    #[test]
+    fn test_cell_letter_constraints()
+    {
+        let mut generator = CrosswordGenerator { words: ["hello", "lot"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        generator.settings.board = Some(Board::new(5, 3));
+        generator.settings.crossword_settings.constraints = vec![
+            CombinedConstraint::CellLetter(CellLetterConstraint::new(WordPosition { x: 3, y: 2 }, 't')),
+            CombinedConstraint::CellLetter(CellLetterConstraint::with_letters(WordPosition { x: 1, y: 0 }, "ae")),
+        ];
+
+        let crosswords = generator.generate_crosswords();
+        assert_eq!(crosswords, BTreeSet::from([
+            Crossword::new_absolute(&[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 3, y: 0 }, direction: WordDirection::Down, value: "lot", id: 0},
+            ]),
+        ]));
+        assert_eq!(crosswords.first().unwrap().get_constrained_letters(&generator.settings.crossword_settings).into_iter().map(|(_, l)| l).collect::<Vec<_>>(), vec![Some('t'), Some('e')]);
+    }
+
+    #[test]
+    fn test_duplicate_words()
+    {
+        let generator = CrosswordGenerator { words: ["cat", "tot", "cat"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+
+        let crosswords: Vec<Crossword> = generator.crossword_iter().collect();
+        assert_eq!(crosswords.len(), crosswords.iter().collect::<BTreeSet<_>>().len());
+        assert!(crosswords.iter().all(|cw| cw.find_words("cat").map(|w| w.id).sorted().collect::<Vec<_>>() == vec![0, 1]));
+        let layout = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+            Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "cat", id: 1},
+        ]);
+        assert_eq!(crosswords.iter().filter(|cw| cw.contains_crossword(&layout) && layout.contains_crossword(cw)).count(), 1);
+        #[cfg(feature = "rec-iter")]
+        assert_eq!(generator.crossword_iter_rec().collect::<BTreeSet<_>>(), crosswords.into_iter().collect::<BTreeSet<_>>());
+
+        let generator = CrosswordGenerator { words: ["cat", "cat"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        assert_eq!(generator.crossword_iter().count(), 3);
+    }
+}
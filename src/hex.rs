@@ -0,0 +1,363 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Serialize, Deserialize};
+
+use super::word::WordCompatibilitySettings;
+use super::lattice::*;
+
+
+/// Represents the position of the first character of a [hex word](HexWord) in axial coordinates
+///
+/// The hexagons are pointy-topped: q grows to the right, r grows down a row (half a cell to the right).
+/// ```text
+/// // (0, 0) (1, 0) (2, 0)
+/// //     (0, 1) (1, 1) (2, 1)
+/// //         (0, 2) (1, 2) (2, 2)
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct HexPosition
+{
+    pub q: isize,
+    pub r: isize
+}
+
+/// Represents the axis of the hex lattice a [hex word](HexWord) runs along
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub enum HexDirection
+{
+    /// Along a row, q grows
+    #[default]
+    East,
+    /// Down and to the right, r grows
+    SouthEast,
+    /// Down and to the left, r grows and q decreases
+    SouthWest
+}
+
+/// All three [hex directions](HexDirection)
+pub const HEX_DIRECTIONS: [HexDirection; 3] = [HexDirection::East, HexDirection::SouthEast, HexDirection::SouthWest];
+
+impl HexDirection
+{
+    /// Returns the change of (q, r) from one character of a word to the next one
+    pub fn get_step(&self) -> (isize, isize)
+    {
+        match *self
+        {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1)
+        }
+    }
+
+    /// Returns the direction whose step is the difference of two neighbouring cells (either way), None if the cells aren't neighbours
+    fn from_neighbour_step(dq: isize, dr: isize) -> Option<HexDirection>
+    {
+        HEX_DIRECTIONS.into_iter().find(|direction|
+        {
+            let step = direction.get_step();
+            step == (dq, dr) || step == (-dq, -dr)
+        })
+    }
+}
+
+/// Represents a word in a [hex crossword](HexCrossword)
+///
+/// Every two neighbouring hexagons share a side and lie on one of the three axes, so two words touch along an axis:
+/// along the axis of both parallel words they touch [head by head](WordCompatibilitySettings::head_by_head),
+/// along the axis of one of two crossing words [side by head](WordCompatibilitySettings::side_by_head), otherwise [side by side](WordCompatibilitySettings::side_by_side).
+/// Hexagons have no corner neighbours, so [corner_by_corner](WordCompatibilitySettings::corner_by_corner) doesn't apply.
+/// The cells next to a crossing on the sides of the 60 degree angle are always neighbours, they are not counted as touching.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct HexWord<'a>
+{
+    pub position: HexPosition,
+    pub direction: HexDirection,
+    pub value: &'a str
+}
+
+impl<'a> HexWord<'a>
+{
+    /// Returns the pairs of character indices of the cells shared by the two [words](HexWord)
+    fn get_shared_cells(&self, other: &HexWord) -> Vec<(usize, usize)>
+    {
+        let other_cells = other.get_cells();
+        self.get_cells().iter().enumerate()
+            .filter_map(|(ind, (position, _))| other_cells.iter().position(|(p, _)| p == position).map(|other_ind| (ind, other_ind)))
+            .collect()
+    }
+
+    /// Returns the axes along which the cells of the two [words](HexWord) are neighbours, leaving out the cells next to their crossing
+    fn get_touching_axes(&self, other: &HexWord) -> BTreeSet<HexDirection>
+    {
+        let shared = self.get_shared_cells(other);
+        let other_cells = other.get_cells();
+        let mut axes = BTreeSet::new();
+        for (ind, (position, _)) in self.get_cells().iter().enumerate()
+        {
+            for (other_ind, (other_position, _)) in other_cells.iter().enumerate()
+            {
+                if shared.iter().any(|(i, j)| ind.abs_diff(*i) <= 1 && other_ind.abs_diff(*j) <= 1) { continue; }
+                if let Some(axis) = HexDirection::from_neighbour_step(other_position.q - position.q, other_position.r - position.r)
+                {
+                    axes.insert(axis);
+                }
+            }
+        }
+        axes
+    }
+}
+
+/// The touching rules of hex words look at the pairs of neighbouring cells of the two words, the axis of every pair tells how they touch
+impl<'a> LatticeWord<'a> for HexWord<'a>
+{
+    type Position = HexPosition;
+    type Direction = HexDirection;
+
+    fn new(position: HexPosition, direction: HexDirection, value: &'a str) -> Self
+    {
+        HexWord { position, direction, value }
+    }
+
+    fn get_direction(&self) -> &HexDirection
+    {
+        &self.direction
+    }
+
+    fn get_value(&self) -> &'a str
+    {
+        self.value
+    }
+
+    fn get_cells(&self) -> Vec<(HexPosition, char)>
+    {
+        let (dq, dr) = self.direction.get_step();
+        self.value.chars().enumerate()
+            .map(|(ind, ch)| (HexPosition { q: self.position.q + dq * ind as isize, r: self.position.r + dr * ind as isize }, ch))
+            .collect()
+    }
+
+    fn get_directions() -> Vec<HexDirection>
+    {
+        HEX_DIRECTIONS.to_vec()
+    }
+
+    /// [East](HexDirection::East) and [SouthEast](HexDirection::SouthEast), a crossword with a [SouthWest](HexDirection::SouthWest) word has a word crossing it along one of them
+    fn get_first_directions() -> Vec<HexDirection>
+    {
+        vec![HexDirection::East, HexDirection::SouthEast]
+    }
+
+    fn get_start_position(cell: &HexPosition, direction: &HexDirection, index: usize) -> HexPosition
+    {
+        let (dq, dr) = direction.get_step();
+        HexPosition { q: cell.q - dq * index as isize, r: cell.r - dr * index as isize }
+    }
+
+    fn get_min_position(first: &HexPosition, second: &HexPosition) -> HexPosition
+    {
+        HexPosition { q: first.q.min(second.q), r: first.r.min(second.r) }
+    }
+
+    fn moved_to_origin(&self, origin: &HexPosition) -> Self
+    {
+        HexWord { position: HexPosition { q: self.position.q - origin.q, r: self.position.r - origin.r }, ..self.clone() }
+    }
+
+    fn intersects(&self, other: &HexWord) -> bool
+    {
+        !self.get_shared_cells(other).is_empty()
+    }
+
+    fn get_intersection_indices(&self, other: &HexWord) -> Option<(usize, usize)>
+    {
+        if self.direction == other.direction { return None; }
+        self.get_shared_cells(other).first().copied()
+    }
+
+    fn head_touches_head(&self, other: &HexWord) -> bool
+    {
+        self.direction == other.direction && self.get_touching_axes(other).contains(&self.direction)
+    }
+
+    fn side_touches_side(&self, other: &HexWord) -> bool
+    {
+        self.get_touching_axes(other).iter().any(|axis| *axis != self.direction && *axis != other.direction)
+    }
+
+    fn side_touches_head(&self, other: &HexWord) -> bool
+    {
+        self.direction != other.direction &&
+        self.get_touching_axes(other).iter().any(|axis| *axis == self.direction || *axis == other.direction)
+    }
+}
+
+/// Represents a crossword on a hex grid, its [words](HexWord) run along the three axes of the lattice
+///
+/// After every change the crossword is [normalized](LatticeCrossword::normalize), so its topmost row is r = 0 and its leftmost diagonal is q = 0.
+pub type HexCrossword<'a> = LatticeCrossword<HexWord<'a>>;
+
+impl<'a> HexCrossword<'a>
+{
+    /// Returns the letters of the crossword by their cells
+    pub fn get_letters(&self) -> BTreeMap<HexPosition, char>
+    {
+        self.get_words().iter().flat_map(|w| w.get_cells()).collect()
+    }
+
+    /// Returns the [words](HexWord) with their clue numbers, cells where words start are numbered row by row like in [get_numbered_words](super::crossword::Crossword::get_numbered_words)
+    pub fn get_numbered_words(&self) -> Vec<(usize, &HexWord<'a>)>
+    {
+        let mut words: Vec<&HexWord<'a>> = self.get_words().iter().collect();
+        words.sort_by_key(|w| (w.position.r, w.position.q, w.direction.clone()));
+
+        let mut result = vec![];
+        let mut last_position: Option<&HexPosition> = None;
+        let mut number = 0;
+        for word in words
+        {
+            if last_position != Some(&word.position)
+            {
+                number += 1;
+                last_position = Some(&word.position);
+            }
+            result.push((number, word));
+        }
+
+        result
+    }
+
+}
+
+/// Generator of [hex crosswords](HexCrossword)
+///
+/// ## Fields
+///
+/// words -> set of strings, the words that will be used to generate hex crosswords, unlike [CrosswordGenerator::words](super::generator::CrosswordGenerator::words)
+/// a word can't be placed twice
+///
+/// word_compatibility_settings -> [WordCompatibilitySettings] applied to the hex words
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct HexCrosswordGenerator
+{
+    pub words: BTreeSet<String>,
+    pub word_compatibility_settings: WordCompatibilitySettings
+}
+
+impl HexCrosswordGenerator
+{
+    /// Generates all possible [hex crosswords](HexCrossword) with all of the [words](HexCrosswordGenerator::words)
+    pub fn generate_crosswords(&self) -> BTreeSet<HexCrossword<'_>>
+    {
+        self.crossword_iter().collect()
+    }
+
+    /// Returns an iterator over all the [hex crosswords](HexCrossword) with all of the [words](HexCrosswordGenerator::words)
+    ///
+    /// Every layout is returned once, even though it can be built by adding the words in several orders.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::hex::HexCrosswordGenerator;
+    /// let generator = HexCrosswordGenerator { words: ["hello", "yo"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+    ///
+    /// assert_eq!(generator.crossword_iter().count(), 6);
+    /// ```
+    pub fn crossword_iter(&self) -> HexCrosswordIterator<'_>
+    {
+        LatticeCrosswordIterator::new(&self.words, &self.word_compatibility_settings, |_: &HexCrossword| true)
+    }
+}
+
+/// Iterator over [hex crosswords](HexCrossword), returned by [crossword_iter](HexCrosswordGenerator::crossword_iter)
+pub type HexCrosswordIterator<'a> = LatticeCrosswordIterator<'a, HexWord<'a>>;
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_hex_word_touching() {
+        let settings = WordCompatibilitySettings::default();
+        let hello = HexWord::new(HexPosition { q: 0, r: 0 }, HexDirection::East, "hello");
+
+        let crossing = HexWord::new(HexPosition { q: 4, r: -1 }, HexDirection::SouthEast, "yo");
+        assert_eq!(hello.get_intersection_indices(&crossing), Some((4, 1)));
+        assert!(!hello.side_touches_side(&crossing));
+        assert!(!hello.side_touches_head(&crossing));
+        assert!(hello.is_compatible_with(&crossing, &settings));
+
+        let crossing_south_west = HexWord::new(HexPosition { q: 2, r: 0 }, HexDirection::SouthWest, "lid");
+        assert_eq!(hello.get_intersection_indices(&crossing_south_west), Some((2, 0)));
+        assert!(hello.is_compatible_with(&crossing_south_west, &settings));
+        assert!(!hello.is_compatible_with(&HexWord::new(HexPosition { q: 1, r: 0 }, HexDirection::SouthWest, "lid"), &settings));
+
+        let head = HexWord::new(HexPosition { q: 5, r: 0 }, HexDirection::East, "world");
+        assert!(hello.head_touches_head(&head));
+        assert!(!hello.is_compatible_with(&head, &settings));
+        assert!(hello.is_compatible_with(&head, &WordCompatibilitySettings { head_by_head: true, ..Default::default() }));
+
+        let below = HexWord::new(HexPosition { q: 0, r: 1 }, HexDirection::East, "world");
+        assert!(below.side_touches_side(&hello));
+        assert!(!below.head_touches_head(&hello));
+
+        let side_head = HexWord::new(HexPosition { q: 2, r: 1 }, HexDirection::SouthEast, "abc");
+        assert!(hello.side_touches_head(&side_head));
+        assert!(!hello.is_compatible_with(&side_head, &WordCompatibilitySettings { side_by_side: true, ..Default::default() }));
+
+        let far = HexWord::new(HexPosition { q: 0, r: 2 }, HexDirection::East, "world");
+        assert!(hello.is_compatible_with(&far, &settings));
+        assert!(!hello.intersects(&far));
+    }
+
+    #[test]
+    fn test_hex_crossword() {
+        let mut cw = HexCrossword::new(&[
+            HexWord::new(HexPosition { q: 0, r: 0 }, HexDirection::East, "hello"),
+            HexWord::new(HexPosition { q: 2, r: 0 }, HexDirection::SouthWest, "lid"),
+        ]);
+
+        assert_eq!(cw.find_word("hello"), Some(&HexWord::new(HexPosition { q: 0, r: 0 }, HexDirection::East, "hello")));
+        assert_eq!(cw.get_letters().get(&HexPosition { q: 0, r: 2 }), Some(&'d'));
+        assert_eq!(cw.get_letters().len(), 7);
+        let numbers: Vec<(usize, &str)> = cw.get_numbered_words().into_iter().map(|(n, w)| (n, w.value)).collect();
+        assert_eq!(numbers, vec![(1, "hello"), (2, "lid")]);
+
+        let json = serde_json::to_string(&cw).unwrap();
+        assert_eq!(serde_json::from_str::<HexCrossword>(&json).unwrap(), cw);
+
+        let ways = cw.calculate_possible_ways_to_add_word("do", &WordCompatibilitySettings::default());
+        assert!(ways.contains(&HexWord::new(HexPosition { q: 0, r: 2 }, HexDirection::East, "do")));
+        assert!(ways.iter().all(|w| cw.can_word_be_added(w, &WordCompatibilitySettings::default())));
+
+        cw.add_word(&HexWord::new(HexPosition { q: 0, r: 2 }, HexDirection::East, "do"));
+        cw.remove_word("hello");
+        assert_eq!(cw.find_word("lid"), Some(&HexWord::new(HexPosition { q: 2, r: 0 }, HexDirection::SouthWest, "lid")));
+    }
+
+    #[test]
+    fn test_hex_generator() {
+        let generator = HexCrosswordGenerator { words: ["hello", "lid", "do"].into_iter().map(|s| s.to_owned()).collect(), ..Default::default() };
+        let crosswords: Vec<HexCrossword> = generator.crossword_iter().collect();
+
+        assert!(!crosswords.is_empty());
+        assert_eq!(crosswords.iter().collect::<BTreeSet<_>>().len(), crosswords.len());
+        assert!(crosswords.contains(&HexCrossword::new(&[
+            HexWord::new(HexPosition { q: 0, r: 0 }, HexDirection::East, "hello"),
+            HexWord::new(HexPosition { q: 2, r: 0 }, HexDirection::SouthWest, "lid"),
+            HexWord::new(HexPosition { q: 0, r: 2 }, HexDirection::East, "do"),
+        ])));
+        for cw in crosswords.iter()
+        {
+            assert_eq!(cw.get_words().len(), 3);
+            assert!(cw.are_words_compatible(&generator.word_compatibility_settings));
+        }
+
+        assert_eq!(HexCrosswordGenerator::default().crossword_iter().count(), 0);
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::generator::*;
+
+
+/// Character that matches any letter in [WordIndex] patterns
+pub const WILDCARD: char = '?';
+
+/// Ids of the words of one length, with the ids of the words that have a given letter at a given position
+#[derive(Clone, Eq, PartialEq, Default, Debug)]
+struct LengthIndex
+{
+    ids: Vec<usize>,
+    by_letter: Vec<BTreeMap<char, Vec<usize>>>
+}
+
+/// Represents an index over a word list for fast pattern queries, built once with [new](WordIndex::new)
+///
+/// Words are grouped by length (in characters), and for every length and position the ids of the words with each letter at that position are stored in increasing order.
+/// A query takes the shortest of the lists of its fixed letters and keeps the ids found in all other lists.
+///
+/// The id of a word is its index in the list the index was built from.
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::index::WordIndex;
+/// let index = WordIndex::new(["cats", "cuts", "cot", "dogs"]);
+///
+/// assert_eq!(index.get_matching("c?ts"), vec!["cats", "cuts"]);
+/// assert_eq!(index.get_with_letter_at(4, 3, 's'), vec!["cats", "cuts", "dogs"]);
+/// ```
+#[derive(Clone, Eq, PartialEq, Default, Debug)]
+pub struct WordIndex
+{
+    words: Vec<String>,
+    by_length: BTreeMap<usize, LengthIndex>
+}
+
+impl WordIndex
+{
+    /// Builds an index over the words, in the given order
+    pub fn new<I, S>(words: I) -> WordIndex
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>
+    {
+        let words: Vec<String> = words.into_iter().map(|w| w.into()).collect();
+        let mut by_length: BTreeMap<usize, LengthIndex> = BTreeMap::new();
+
+        for (id, word) in words.iter().enumerate()
+        {
+            let length = word.chars().count();
+            let length_index = by_length.entry(length).or_insert_with(|| LengthIndex { ids: vec![], by_letter: vec![BTreeMap::new(); length] });
+
+            length_index.ids.push(id);
+            for (position, ch) in word.chars().enumerate()
+            {
+                length_index.by_letter[position].entry(ch).or_default().push(id);
+            }
+        }
+
+        WordIndex { words, by_length }
+    }
+
+    /// Returns the number of indexed words
+    pub fn len(&self) -> usize
+    {
+        self.words.len()
+    }
+
+    /// Returns true if there are no indexed words
+    pub fn is_empty(&self) -> bool
+    {
+        self.words.is_empty()
+    }
+
+    /// Returns the word with the given id
+    pub fn get_word(&self, id: usize) -> Option<&str>
+    {
+        self.words.get(id).map(|w| w.as_str())
+    }
+
+    /// Returns the ids of all words of the given length, in increasing order
+    pub fn get_ids_with_length(&self, length: usize) -> &[usize]
+    {
+        self.by_length.get(&length).map_or(&[], |l| l.ids.as_slice())
+    }
+
+    /// Returns the ids of the words of the given length that have the letter at the position (starting from 0), in increasing order
+    pub fn get_ids_with_letter_at(&self, length: usize, position: usize, letter: char) -> &[usize]
+    {
+        self.by_length.get(&length)
+            .and_then(|l| l.by_letter.get(position))
+            .and_then(|letters| letters.get(&letter))
+            .map_or(&[], |ids| ids.as_slice())
+    }
+
+    /// Returns the words of the given length that have the letter at the position (starting from 0)
+    pub fn get_with_letter_at(&self, length: usize, position: usize, letter: char) -> Vec<&str>
+    {
+        self.get_ids_with_letter_at(length, position, letter).iter().map(|id| self.words[*id].as_str()).collect()
+    }
+
+    /// Returns the ids of the words that match the pattern, in increasing order
+    ///
+    /// A pattern matches words of its length, every [WILDCARD] matches any letter and every other character matches only itself.
+    pub fn get_matching_ids(&self, pattern: &str) -> Vec<usize>
+    {
+        let length = pattern.chars().count();
+        let mut lists: Vec<&[usize]> = pattern.chars().enumerate()
+            .filter(|(_, ch)| *ch != WILDCARD)
+            .map(|(position, ch)| self.get_ids_with_letter_at(length, position, ch))
+            .collect();
+
+        if lists.is_empty()
+        {
+            return self.get_ids_with_length(length).to_vec();
+        }
+
+        lists.sort_by_key(|ids| ids.len());
+        let (shortest, others) = lists.split_first().expect("lists is not empty");
+
+        shortest.iter()
+            .copied()
+            .filter(|id| others.iter().all(|ids| ids.binary_search(id).is_ok()))
+            .collect()
+    }
+
+    /// Returns the words that match the pattern, see [get_matching_ids](WordIndex::get_matching_ids)
+    pub fn get_matching(&self, pattern: &str) -> Vec<&str>
+    {
+        self.get_matching_ids(pattern).into_iter().map(|id| self.words[id].as_str()).collect()
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for WordIndex
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self
+    {
+        WordIndex::new(iter)
+    }
+}
+
+impl CrosswordGenerator
+{
+    /// Loads the words of the [index](WordIndex) that match the pattern into the [words](CrosswordGenerator::words) of the generator, words that are already there are skipped
+    ///
+    /// This is a bulk loader, not a source of optional candidates: every loaded word has to be placed in every generated crossword,
+    /// so the pattern should select only a few words. The index is not queried during generation.
+    ///
+    /// Returns the number of words that were loaded.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::generator::CrosswordGenerator;
+    /// # use crossword_generator::index::WordIndex;
+    /// let index = WordIndex::new(["cats", "cuts", "cot", "dogs"]);
+    /// let mut generator = CrosswordGenerator::default();
+    ///
+    /// assert_eq!(generator.load_words_from_index(&index, "c?ts"), 2);
+    /// assert_eq!(generator.load_words_from_index(&index, "?o??"), 1);
+    /// assert_eq!(generator.words.len(), 3);
+    /// ```
+    pub fn load_words_from_index(&mut self, index: &WordIndex, pattern: &str) -> usize
+    {
+        let mut known: BTreeSet<&str> = self.words.iter().map(|w| w.as_str()).collect();
+        let words: Vec<String> = index.get_matching(pattern).into_iter()
+            .filter(|word| known.insert(word))
+            .map(|word| word.to_owned())
+            .collect();
+        self.words.extend(words.iter().cloned());
+        words.len()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_word_index_get_matching() {
+        let index: WordIndex = ["cats", "cuts", "coats", "cites", "cures", "cot", "", "crêpe"].into_iter().collect();
+
+        assert_eq!(index.len(), 8);
+        assert_eq!(index.get_matching("c?t?s"), vec!["cites"]);
+        assert_eq!(index.get_matching("?????"), vec!["coats", "cites", "cures", "crêpe"]);
+        assert_eq!(index.get_matching("cre??"), Vec::<&str>::new());
+        assert_eq!(index.get_matching("cr??e"), vec!["crêpe"]);
+        assert_eq!(index.get_matching("cats"), vec!["cats"]);
+        assert_eq!(index.get_matching("??????"), Vec::<&str>::new());
+        assert_eq!(index.get_matching(""), vec![""]);
+        assert_eq!(index.get_matching_ids("c??s"), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_word_index_get_with_letter_at() {
+        let index = WordIndex::new(["cats", "cuts", "dots", "cot"]);
+
+        assert_eq!(index.get_with_letter_at(4, 2, 't'), vec!["cats", "cuts", "dots"]);
+        assert_eq!(index.get_ids_with_letter_at(4, 0, 'c'), &[0, 1]);
+        assert_eq!(index.get_ids_with_letter_at(3, 3, 'c'), &[] as &[usize]);
+        assert_eq!(index.get_ids_with_letter_at(7, 0, 'c'), &[] as &[usize]);
+        assert_eq!(index.get_ids_with_length(3), &[3]);
+        assert_eq!(index.get_word(2), Some("dots"));
+        assert_eq!(index.get_word(4), None);
+    }
+
+    #[test]
+    fn test_load_words_from_index() {
+        let index = WordIndex::new(["cats", "cuts", "cats", "dots"]);
+        let mut generator = CrosswordGenerator { words: vec!["cuts".to_owned()], ..Default::default() };
+
+        assert_eq!(generator.load_words_from_index(&index, "??ts"), 2);
+        assert_eq!(generator.words, vec!["cuts", "cats", "dots"]);
+        assert_eq!(generator.load_words_from_index(&index, "c?ts"), 0);
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+
+
+/// Represents which variant of a [crossword](Crossword) is written by [generate_latex](Crossword::generate_latex)
+///
+/// [Puzzle](LatexVariant::Puzzle) leaves the cells empty (only the clue numbers are shown),
+/// [Solution](LatexVariant::Solution) fills the cells with the letters of the words.
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub enum LatexVariant
+{
+    #[default]
+    Puzzle,
+    Solution
+}
+
+/// Represents settings for exporting a [crossword](Crossword) as LaTeX with [generate_latex](Crossword::generate_latex)
+///
+/// ## Fields
+///
+/// variant -> [LatexVariant], puzzle or solution
+///
+/// standalone -> if true, a whole document (with `\documentclass` and `\usepackage{cwpuzzle}`) is written, otherwise only a fragment that can be `\input` into another document
+///
+/// empty_cells_as_blocks -> if true, cells without letters are drawn as black squares, otherwise they are left out of the grid
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct LatexSettings
+{
+    pub variant: LatexVariant,
+    pub standalone: bool,
+    pub empty_cells_as_blocks: bool
+}
+
+impl Default for LatexSettings
+{
+    fn default() -> Self
+    {
+        LatexSettings
+        {
+            variant: LatexVariant::Puzzle,
+            standalone: true,
+            empty_cells_as_blocks: false
+        }
+    }
+}
+
+/// Returns the title of the list of clues the words in the direction belong to, diagonal words get their own list
+fn get_clue_group(direction: &WordDirection) -> &'static str
+{
+    if direction.is_horizontal() { "Across" } else if direction.is_vertical() { "Down" } else { "Diagonal" }
+}
+
+/// Escapes the characters that have a special meaning in LaTeX
+fn escape_latex(text: &str) -> String
+{
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars()
+    {
+        match ch
+        {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => { result.push('\\'); result.push(ch); },
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            '\\' => result.push_str("\\textbackslash{}"),
+            _ => result.push(ch)
+        }
+    }
+
+    result
+}
+
+/// Returns the LaTeX of the letter of a grid cell, so that it stays a single cell of the `cwpuzzle` grid
+///
+/// The letter is written in upper case, unless its upper case has several characters (like 'ß'), then it is written as it is.
+/// A letter that has to be [escaped](escape_latex) is grouped in braces.
+fn get_cell_latex(ch: char) -> String
+{
+    let mut upper = ch.to_uppercase();
+    let letter = match (upper.next(), upper.next())
+    {
+        (Some(upper), None) => upper,
+        _ => ch
+    };
+
+    let escaped = escape_latex(&letter.to_string());
+    if escaped.chars().count() == 1 { escaped } else { format!("{{{}}}", escaped) }
+}
+
+impl<'a> Crossword<'a>
+{
+    /// Returns a LaTeX representation of the [crossword](Crossword), following the conventions of the `cwpuzzle` package
+    ///
+    /// The grid is built with [generate_char_table](Crossword::generate_char_table) and the cells are numbered with [get_numbered_words](Crossword::get_numbered_words).
+    /// After the grid, the Across and Down clues are listed, clue texts are taken from `clues` by the [clue key](Word::get_clue_key) of the [word](Word), or by its string value if the key is missing (missing clues are left empty).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// # use crossword_generator::latex::{LatexSettings, LatexVariant};
+    /// # use std::collections::BTreeMap;
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},      //    |    o|
+    /// ]);                                                                                                        //    |    y|
+    ///                                                                                                            //     -----
+    /// let clues = BTreeMap::from([("cat".to_owned(), "Meows".to_owned()), ("toy".to_owned(), "Plaything".to_owned())]);
+    /// let settings = LatexSettings { variant: LatexVariant::Puzzle, standalone: false, ..Default::default() };
+    ///
+    /// assert_eq!(cw.generate_latex(&clues, &settings),
+    /// "\
+    /// \\PuzzleUnsolved
+    /// \\begin{Puzzle}{3}{3}
+    /// |[1]C |A |[2]T |.
+    /// |{} |{} |O |.
+    /// |{} |{} |Y |.
+    /// \\end{Puzzle}
+    ///
+    /// \\begin{PuzzleClues}{\\textbf{Across}}
+    /// \\Clue{1}{CAT}{Meows}\\\\
+    /// \\end{PuzzleClues}
+    ///
+    /// \\begin{PuzzleClues}{\\textbf{Down}}
+    /// \\Clue{2}{TOY}{Plaything}\\\\
+    /// \\end{PuzzleClues}
+    /// ");
+    /// ```
+    pub fn generate_latex(&self, clues: &BTreeMap<String, String>, settings: &LatexSettings) -> String
+    {
+        let table = self.generate_char_table();
+        let size = self.get_size();
+        let numbered_words = self.get_numbered_words();
+        let numbers = self.get_cell_numbers();
+
+        let mut result = String::new();
+
+        if settings.standalone
+        {
+            result.push_str("\\documentclass{article}\n\\usepackage{cwpuzzle}\n\\begin{document}\n\n");
+        }
+
+        result.push_str(match settings.variant
+        {
+            LatexVariant::Puzzle => "\\PuzzleUnsolved\n",
+            LatexVariant::Solution => "\\PuzzleSolution\n",
+        });
+
+        result.push_str(&format!("\\begin{{Puzzle}}{{{}}}{{{}}}\n", size.0, size.1));
+        for (y, row) in table.iter().enumerate()
+        {
+            for (x, ch) in row.iter().enumerate()
+            {
+                result.push('|');
+                if *ch == ' '
+                {
+                    result.push_str(if settings.empty_cells_as_blocks { "* " } else { "{} " });
+                    continue;
+                }
+                if let Some(n) = numbers.get(&(x, y))
+                {
+                    result.push_str(&format!("[{}]", n));
+                }
+                result.push_str(&get_cell_latex(*ch));
+                result.push(' ');
+            }
+            result.push_str("|.\n");
+        }
+        result.push_str("\\end{Puzzle}\n");
+
+        for title in ["Across", "Down", "Diagonal"]
+        {
+            let group: Vec<&(usize, &Word)> = numbered_words.iter().filter(|(_, w)| get_clue_group(&w.direction) == title).collect();
+            if title == "Diagonal" && group.is_empty() { continue; }
+
+            result.push_str(&format!("\n\\begin{{PuzzleClues}}{{\\textbf{{{}}}}}\n", title));
+            for (n, word) in group
+            {
+                let clue = clues.get(&word.get_clue_key()).or_else(|| clues.get(word.value)).map(|c| escape_latex(c)).unwrap_or_default();
+                result.push_str(&format!("\\Clue{{{}}}{{{}}}{{{}}}\\\\\n", n, escape_latex(&word.value.to_uppercase()), clue));
+            }
+            result.push_str("\\end{PuzzleClues}\n");
+        }
+
+        if settings.standalone
+        {
+            result.push_str("\n\\end{document}\n");
+        }
+
+        result
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_crossword_generate_latex() {
+        let cw = Crossword::new(
+            &[
+                Word{position: WordPosition { x: -1, y: -1 }, direction: WordDirection::Right, value: "hello", id: 0},
+                Word{position: WordPosition { x: 1, y: -1 }, direction: WordDirection::Down, value: "local", id: 0},
+                Word{position: WordPosition { x: 1, y: 1 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 1 }, direction: WordDirection::Down, value: "and", id: 0},
+                Word{position: WordPosition { x: 3, y: 1 }, direction: WordDirection::Down, value: "toy", id: 0},
+
+            ]);
+
+        let clues = BTreeMap::from([("hello".to_owned(), "Greeting & welcome".to_owned()), ("toy".to_owned(), "Plaything".to_owned())]);
+
+        assert_eq!(cw.generate_latex(&clues, &LatexSettings { variant: LatexVariant::Solution, standalone: true, empty_cells_as_blocks: true }),
+        "\
+\\documentclass{article}
+\\usepackage{cwpuzzle}
+\\begin{document}
+
+\\PuzzleSolution
+\\begin{Puzzle}{5}{5}
+|[1]H |E |[2]L |L |O |.
+|* |* |O |* |* |.
+|* |* |[3]C |[4]A |[5]T |.
+|* |* |A |N |O |.
+|* |* |L |D |Y |.
+\\end{Puzzle}
+
+\\begin{PuzzleClues}{\\textbf{Across}}
+\\Clue{1}{HELLO}{Greeting \\& welcome}\\\\
+\\Clue{3}{CAT}{}\\\\
+\\end{PuzzleClues}
+
+\\begin{PuzzleClues}{\\textbf{Down}}
+\\Clue{2}{LOCAL}{}\\\\
+\\Clue{4}{AND}{}\\\\
+\\Clue{5}{TOY}{Plaything}\\\\
+\\end{PuzzleClues}
+
+\\end{document}
+");
+    }
+
+    #[test]
+    fn test_duplicate_word_clues() {
+        let cw = Crossword::new(&[
+            Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+            Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "tot", id: 0},
+            Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "cat", id: 1},
+        ]);
+
+        let clues = BTreeMap::from([("cat".to_owned(), "Meows".to_owned()), ("cat#1".to_owned(), "Pet".to_owned()), ("tot".to_owned(), "Small child".to_owned())]);
+
+        assert_eq!(cw.generate_latex(&clues, &LatexSettings { variant: LatexVariant::Puzzle, standalone: false, empty_cells_as_blocks: false }),
+        "\
+\\PuzzleUnsolved
+\\begin{Puzzle}{3}{3}
+|[1]C |A |[2]T |.
+|{} |{} |O |.
+|[3]C |A |T |.
+\\end{Puzzle}
+
+\\begin{PuzzleClues}{\\textbf{Across}}
+\\Clue{1}{CAT}{Meows}\\\\
+\\Clue{3}{CAT}{Pet}\\\\
+\\end{PuzzleClues}
+
+\\begin{PuzzleClues}{\\textbf{Down}}
+\\Clue{2}{TOT}{Small child}\\\\
+\\end{PuzzleClues}
+");
+    }
+
+    #[test]
+    fn test_special_cell_letters() {
+        let cw = Crossword::new(&[Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "ß&~é", id: 0}]);
+
+        assert_eq!(cw.generate_latex(&BTreeMap::new(), &LatexSettings { variant: LatexVariant::Solution, standalone: false, empty_cells_as_blocks: false }),
+        "\
+\\PuzzleSolution
+\\begin{Puzzle}{4}{1}
+|[1]ß |{\\&} |{\\textasciitilde{}} |É |.
+\\end{Puzzle}
+
+\\begin{PuzzleClues}{\\textbf{Across}}
+\\Clue{1}{SS\\&\\textasciitilde{}É}{}\\\\
+\\end{PuzzleClues}
+
+\\begin{PuzzleClues}{\\textbf{Down}}
+\\end{PuzzleClues}
+");
+    }
+
+    #[test]
+    fn test_escape_latex() {
+        assert_eq!(escape_latex("50% of $x_1$ {a} #1 ~ ^ \\"), "50\\% of \\$x\\_1\\$ \\{a\\} \\#1 \\textasciitilde{} \\textasciicircum{} \\textbackslash{}");
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::BTreeSet;
+
+use serde::{Serialize, Deserialize};
+
+use super::word::WordCompatibilitySettings;
+
+
+/// A word on a lattice of cells other than the square grid of [Word](super::word::Word), for example a [cube word](super::cube::CubeWord) or a [hex word](super::hex::HexWord)
+///
+/// The geometry of the lattice is given by the required methods, the [compatibility](LatticeWord::is_compatible_with) of two words and
+/// the [ways to cross](LatticeWord::calculate_possible_ways_to_add_word) a word are built on top of them the same way for every lattice.
+pub trait LatticeWord<'a>: Clone + Ord
+{
+    /// Position of a cell of the lattice
+    type Position: Clone + Ord + Default;
+    /// Axis of the lattice a word runs along
+    type Direction: Clone + Eq;
+
+    /// Creates a word whose first character is in the cell
+    fn new(position: Self::Position, direction: Self::Direction, value: &'a str) -> Self;
+
+    /// Returns the direction of the word
+    fn get_direction(&self) -> &Self::Direction;
+
+    /// Returns the string value of the word
+    fn get_value(&self) -> &'a str;
+
+    /// Returns the positions of the characters of the word with the characters
+    fn get_cells(&self) -> Vec<(Self::Position, char)>;
+
+    /// Returns all the directions of the lattice
+    fn get_directions() -> Vec<Self::Direction>;
+
+    /// Returns the directions of the first word of an empty [crossword](LatticeCrossword), every connected crossword with two or more words has a word along one of them
+    fn get_first_directions() -> Vec<Self::Direction>;
+
+    /// Returns the position of the first character of a word in the direction, such that its character with the index is in the cell
+    fn get_start_position(cell: &Self::Position, direction: &Self::Direction, index: usize) -> Self::Position;
+
+    /// Returns the position with the smaller coordinate of the two positions on every axis
+    fn get_min_position(first: &Self::Position, second: &Self::Position) -> Self::Position;
+
+    /// Returns the word moved so the origin ends up at the position with all coordinates 0
+    fn moved_to_origin(&self, origin: &Self::Position) -> Self;
+
+    /// Returns true if the words have a common cell
+    fn intersects(&self, other: &Self) -> bool;
+
+    /// Returns indices of the common character of two crossing words, None if they don't cross
+    fn get_intersection_indices(&self, other: &Self) -> Option<(usize, usize)>;
+
+    /// Returns true if the words touch only by corners (check [WordCompatibilitySettings::corner_by_corner]), never on lattices without corner neighbours
+    fn corners_touch(&self, _other: &Self) -> bool
+    {
+        false
+    }
+
+    /// Returns true if two parallel words lie on the same line and one starts right after the other ends (check [WordCompatibilitySettings::head_by_head])
+    fn head_touches_head(&self, other: &Self) -> bool;
+
+    /// Returns true if the words touch along an axis that is not the direction of any of them (check [WordCompatibilitySettings::side_by_side])
+    fn side_touches_side(&self, other: &Self) -> bool;
+
+    /// Returns true if the head of one of two non parallel words touches the side of the other one (check [WordCompatibilitySettings::side_by_head])
+    fn side_touches_head(&self, other: &Self) -> bool;
+
+    /// Checks if two words can be in the same [crossword](LatticeCrossword) with the [settings](WordCompatibilitySettings)
+    fn is_compatible_with(&self, other: &Self, settings: &WordCompatibilitySettings) -> bool
+    {
+        if self.corners_touch(other) && !settings.corner_by_corner { return false; }
+        if self.head_touches_head(other) && !settings.head_by_head { return false; }
+        if self.side_touches_side(other) && !settings.side_by_side { return false; }
+        if self.side_touches_head(other) && !settings.side_by_head { return false; }
+        if self.intersects(other)
+        {
+            return match self.get_intersection_indices(other)
+            {
+                Some((first_ind, second_ind)) => self.get_value().chars().nth(first_ind) == other.get_value().chars().nth(second_ind),
+                None => false
+            };
+        }
+
+        true
+    }
+
+    /// Returns all possible ways to cross this word with another word along one of the other directions
+    fn calculate_possible_ways_to_add_word(&self, word: &'a str) -> BTreeSet<Self>
+    {
+        let mut ways = BTreeSet::new();
+        for direction in Self::get_directions().into_iter().filter(|direction| direction != self.get_direction())
+        {
+            for (position, ch) in self.get_cells()
+            {
+                for (ind, _) in word.chars().enumerate().filter(|(_, other_ch)| *other_ch == ch)
+                {
+                    ways.insert(Self::new(Self::get_start_position(&position, &direction, ind), direction.clone(), word));
+                }
+            }
+        }
+        ways
+    }
+}
+
+/// Represents a crossword of [lattice words](LatticeWord), the common part of [cube crosswords](super::cube::CubeCrossword) and [hex crosswords](super::hex::HexCrossword)
+///
+/// Like a relative [crossword](super::crossword::Crossword), it is [normalized](LatticeCrossword::normalize) after every change,
+/// so the smallest coordinates of its cells on every axis are 0. Every string value is in it at most once.
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "W: Deserialize<'de> + Ord"))]
+pub struct LatticeCrossword<W>
+{
+    words: BTreeSet<W>
+}
+
+impl<'a, W: LatticeWord<'a>> LatticeCrossword<W>
+{
+    /// Creates a new crossword with the given [words](LatticeWord) and [normalizes](LatticeCrossword::normalize) it, the words are not checked
+    pub fn new(words: &[W]) -> LatticeCrossword<W>
+    {
+        let mut cw = LatticeCrossword { words: words.iter().cloned().collect() };
+        cw.normalize();
+        cw
+    }
+
+    /// Moves all [words](LatticeWord) so the smallest coordinates of their cells on every axis are 0
+    pub fn normalize(&mut self)
+    {
+        let origin = self.words.iter()
+            .flat_map(|w| w.get_cells())
+            .map(|(position, _)| position)
+            .reduce(|first, second| W::get_min_position(&first, &second))
+            .unwrap_or_default();
+
+        self.words = self.words.iter().map(|word| word.moved_to_origin(&origin)).collect();
+    }
+
+    /// Adds the [word](LatticeWord) if there is no other word with the same string value and [normalizes](LatticeCrossword::normalize) the crossword
+    pub fn add_word(&mut self, word: &W)
+    {
+        if self.find_word(word.get_value()).is_some() { return; }
+        self.words.insert(word.clone());
+        self.normalize();
+    }
+
+    /// Removes the [word](LatticeWord) with the string value if found and [normalizes](LatticeCrossword::normalize) the crossword
+    pub fn remove_word(&mut self, word: &str)
+    {
+        if let Some(word) = self.find_word(word).cloned()
+        {
+            self.words.remove(&word);
+            self.normalize();
+        }
+    }
+
+    /// Returns the [word](LatticeWord) with the string value
+    pub fn find_word(&self, word: &str) -> Option<&W>
+    {
+        self.words.iter().find(|w| w.get_value() == word)
+    }
+
+    /// Returns the [words](LatticeWord) of the crossword
+    pub fn get_words(&self) -> &BTreeSet<W>
+    {
+        &self.words
+    }
+
+    /// Checks if every two [words](LatticeWord) of the crossword are [compatible](LatticeWord::is_compatible_with) with the settings
+    pub fn are_words_compatible(&self, settings: &WordCompatibilitySettings) -> bool
+    {
+        let words: Vec<&W> = self.words.iter().collect();
+        words.iter().enumerate().all(|(ind, first)| words[ind + 1..].iter().all(|second| first.is_compatible_with(second, settings)))
+    }
+
+    /// Checks if the [word](LatticeWord) can be added: no word with the same value is in the crossword and the word is [compatible](LatticeWord::is_compatible_with) with all of them
+    pub fn can_word_be_added(&self, word: &W, settings: &WordCompatibilitySettings) -> bool
+    {
+        self.find_word(word.get_value()).is_none() && self.words.iter().all(|w| w.is_compatible_with(word, settings))
+    }
+
+    /// Returns all possible ways to add the word to the crossword by crossing one of its [words](LatticeWord),
+    /// the first word of an empty crossword is placed in the [first directions](LatticeWord::get_first_directions)
+    pub fn calculate_possible_ways_to_add_word(&self, word: &'a str, settings: &WordCompatibilitySettings) -> BTreeSet<W>
+    {
+        if self.words.is_empty()
+        {
+            return W::get_first_directions().into_iter().map(|direction| W::new(W::Position::default(), direction, word)).collect();
+        }
+
+        self.words.iter()
+            .flat_map(|w| w.calculate_possible_ways_to_add_word(word))
+            .filter(|step| self.can_word_be_added(step, settings))
+            .collect()
+    }
+}
+
+/// Tells if a partial crossword can still be extended, see [LatticeCrosswordIterator::new]
+type CrosswordFilter<'a, W> = Box<dyn Fn(&LatticeCrossword<W>) -> bool + 'a>;
+
+struct LatticeFrame<'a, W>
+{
+    crossword: LatticeCrossword<W>,
+    remained_words: BTreeSet<&'a str>,
+    steps: Vec<W>
+}
+
+/// Iterator over the [lattice crosswords](LatticeCrossword) with all of the given words
+///
+/// Like [crossword_iter](super::generator::CrosswordGenerator::crossword_iter), the algorithm is not recursive, it keeps a stack of frames
+/// with the steps that are left to try, and every partial crossword is extended only once.
+pub struct LatticeCrosswordIterator<'a, W>
+{
+    settings: WordCompatibilitySettings,
+    fits: CrosswordFilter<'a, W>,
+    visited: BTreeSet<LatticeCrossword<W>>,
+    frame_stack: Vec<LatticeFrame<'a, W>>
+}
+
+impl<'a, W: LatticeWord<'a>> LatticeCrosswordIterator<'a, W>
+{
+    /// Creates an iterator over the crosswords with all of the words, partial crosswords for which `fits` returns false are not extended
+    pub(crate) fn new(words: &'a BTreeSet<String>, settings: &WordCompatibilitySettings, fits: impl Fn(&LatticeCrossword<W>) -> bool + 'a) -> LatticeCrosswordIterator<'a, W>
+    {
+        let remained_words: BTreeSet<&str> = words.iter().map(|s| s.as_str()).collect();
+        let crossword = LatticeCrossword { words: BTreeSet::new() };
+        let steps = remained_words.iter().flat_map(|word| crossword.calculate_possible_ways_to_add_word(word, settings)).collect();
+
+        LatticeCrosswordIterator
+        {
+            settings: settings.clone(),
+            fits: Box::new(fits),
+            visited: BTreeSet::new(),
+            frame_stack: if remained_words.is_empty() { vec![] } else { vec![LatticeFrame { crossword, remained_words, steps }] }
+        }
+    }
+}
+
+impl<'a, W: LatticeWord<'a>> Iterator for LatticeCrosswordIterator<'a, W>
+{
+    type Item = LatticeCrossword<W>;
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        loop
+        {
+            let frame = self.frame_stack.last_mut()?;
+            let step = match frame.steps.pop()
+            {
+                Some(step) => step,
+                None =>
+                {
+                    self.frame_stack.pop();
+                    continue;
+                }
+            };
+
+            let mut crossword = frame.crossword.clone();
+            crossword.add_word(&step);
+            if !self.visited.insert(crossword.clone()) { continue; }
+            if !(self.fits)(&crossword) { continue; }
+
+            let mut remained_words = frame.remained_words.clone();
+            remained_words.remove(step.get_value());
+            if remained_words.is_empty()
+            {
+                return Some(crossword);
+            }
+
+            let steps = remained_words.iter().flat_map(|word| crossword.calculate_possible_ways_to_add_word(word, &self.settings)).collect();
+            self.frame_stack.push(LatticeFrame { crossword, remained_words, steps });
+        }
+    }
+}
@@ -4,4 +4,19 @@
 
 pub mod word;
 pub mod crossword;
+pub mod constraint;
+pub mod board;
 pub mod generator;
+pub mod config;
+pub mod latex;
+pub mod svg;
+pub mod index;
+pub mod fill;
+pub mod pattern;
+pub mod word_search;
+pub mod lattice;
+pub mod cube;
+pub mod hex;
+pub mod edit;
+#[cfg(feature = "png")]
+pub mod raster;
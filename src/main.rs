@@ -1,12 +1,607 @@
-use crossword_generator::{generator::{CrosswordGenerator, CrosswordGeneratorSettings}, crossword::CrosswordSizeConstraint};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
-fn main()
+use crossword_generator::{generator::{CrosswordGenerator, CrosswordGeneratorSettings}, crossword::{Crossword, CrosswordSizeConstraint}, word::WordDirection};
+use crossword_generator::board::Board;
+use crossword_generator::cube::{CubeCrossword, CubeCrosswordGenerator};
+use crossword_generator::constraint::{CombinedConstraint, CrossingConstraint, ShapeMask};
+use crossword_generator::latex::{LatexSettings, LatexVariant};
+use crossword_generator::svg::SvgSettings;
+use crossword_generator::word_search::{LetterSource, WordSearch, WordSearchSettings};
+
+
+/// Generates crosswords from word lists and converts them between formats
+#[derive(Parser)]
+#[command(name = "crossword-generator", version, about)]
+struct Cli
+{
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Subcommand)]
+enum Command
+{
+    /// Generates crosswords from a word list file (one word per line, empty lines and lines starting with '#' are skipped)
+    Generate(GenerateArgs),
+    /// Converts a saved crossword (in JSON format) to another format
+    Render(RenderArgs),
+    /// Checks if a saved crossword (in JSON format) is correct and satisfies the settings
+    Validate(ValidateArgs),
+    /// Prints statistics of a saved crossword (in JSON format)
+    Stats(StatsArgs),
+    /// Generates a word search puzzle from a word list file (in the same format as for generate)
+    WordSearch(WordSearchArgs),
+    /// Generates three-dimensional crosswords from a word list file, words run along X, Y or Z
+    Cube(CubeArgs),
+}
+
+/// Output format of crosswords
+#[derive(Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+enum Format
+{
+    /// The boxed grid of Crossword::generate_string
+    #[default]
+    Text,
+    /// Serialized crossword(s)
+    Json,
+    /// SVG drawing of the grid
+    Svg,
+    /// LaTeX document for the cwpuzzle package
+    Latex,
+    /// PNG image of the grid
+    #[cfg(feature = "png")]
+    Png,
+}
+
+impl Format
+{
+    fn extension(&self) -> &'static str
+    {
+        match self
+        {
+            Format::Text => "txt",
+            Format::Json => "json",
+            Format::Svg => "svg",
+            Format::Latex => "tex",
+            #[cfg(feature = "png")]
+            Format::Png => "png",
+        }
+    }
+}
+
+#[derive(Args)]
+struct SettingsArgs
+{
+    /// Built-in settings preset (default, newspaper-compact, kids-loose)
+    #[arg(long, conflicts_with = "config")]
+    preset: Option<String>,
+    /// Settings file (.toml for TOML, JSON otherwise), it can choose a base preset with a "preset" key, the flags below override its values
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Allow parallel words to touch side by side
+    #[arg(long)]
+    side_by_side: Option<bool>,
+    /// Allow parallel words to touch head by head
+    #[arg(long)]
+    head_by_head: Option<bool>,
+    /// Allow perpendicular words to touch side by head
+    #[arg(long)]
+    side_by_head: Option<bool>,
+    /// Allow words to touch corner by corner
+    #[arg(long)]
+    corner_by_corner: Option<bool>,
+    /// Maximum length (width) of the crossword
+    #[arg(long)]
+    max_length: Option<usize>,
+    /// Maximum height of the crossword
+    #[arg(long)]
+    max_height: Option<usize>,
+    /// Maximum area of the crossword
+    #[arg(long)]
+    max_area: Option<usize>,
+    /// Minimum length (width) of the crossword
+    #[arg(long)]
+    min_length: Option<usize>,
+    /// Minimum height of the crossword
+    #[arg(long)]
+    min_height: Option<usize>,
+    /// Maximum number of cells with letters in the crossword
+    #[arg(long)]
+    max_cells: Option<usize>,
+    /// Minimum number of words that every word crosses
+    #[arg(long)]
+    min_crossings: Option<usize>,
+    /// Minimum percentage of the cells with letters that are shared by two words
+    #[arg(long)]
+    min_checked_percentage: Option<usize>,
+    /// ASCII art file with the shape the crossword has to fit in (' ' and '.' are outside of it)
+    #[arg(long)]
+    mask: Option<PathBuf>,
+    /// ASCII art file with a fixed board, words are placed only on its cells that are not '#'
+    #[arg(long)]
+    board: Option<PathBuf>,
+    /// Make the board a torus, words that run off one edge continue on the opposite edge
+    #[arg(long, requires = "board")]
+    wrap: bool,
+    /// Directions the words can be placed in, separated by commas (right, down, left, up, down-right, up-right, down-left, up-left)
+    #[arg(long, value_delimiter = ',', value_parser = parse_direction)]
+    directions: Option<Vec<WordDirection>>,
+}
+
+impl SettingsArgs
+{
+    fn load(&self) -> Result<CrosswordGeneratorSettings, String>
+    {
+        let mut settings = match (&self.config, &self.preset)
+        {
+            (Some(path), _) => CrosswordGeneratorSettings::from_file(path).map_err(|e| e.to_string())?,
+            (None, Some(preset)) => CrosswordGeneratorSettings::preset(preset).map_err(|e| e.to_string())?,
+            (None, None) => CrosswordGeneratorSettings::default()
+        };
+
+        let compatibility = &mut settings.word_compatibility_settings;
+        if let Some(v) = self.side_by_side { compatibility.side_by_side = v; }
+        if let Some(v) = self.head_by_head { compatibility.head_by_head = v; }
+        if let Some(v) = self.side_by_head { compatibility.side_by_head = v; }
+        if let Some(v) = self.corner_by_corner { compatibility.corner_by_corner = v; }
+
+        let constraints = &mut settings.crossword_settings.size_constraints;
+        if let Some(v) = self.max_length { constraints.push(CrosswordSizeConstraint::MaxLength(v)); }
+        if let Some(v) = self.max_height { constraints.push(CrosswordSizeConstraint::MaxHeight(v)); }
+        if let Some(v) = self.max_area { constraints.push(CrosswordSizeConstraint::MaxArea(v)); }
+        if let Some(v) = self.min_length { constraints.push(CrosswordSizeConstraint::MinLength(v)); }
+        if let Some(v) = self.min_height { constraints.push(CrosswordSizeConstraint::MinHeight(v)); }
+        if let Some(v) = self.max_cells { constraints.push(CrosswordSizeConstraint::MaxCells(v)); }
+
+        let constraints = &mut settings.crossword_settings.constraints;
+        if let Some(v) = self.min_crossings { constraints.push(CombinedConstraint::Crossing(CrossingConstraint::MinCrossingsPerWord(v))); }
+        if let Some(v) = self.min_checked_percentage { constraints.push(CombinedConstraint::Crossing(CrossingConstraint::MinCheckedCellRatio(v, 100))); }
+
+        if let Some(path) = &self.mask
+        {
+            let mask = read_file(path)?;
+            settings.crossword_settings.constraints.push(CombinedConstraint::Mask(ShapeMask::from_ascii(&mask)));
+        }
+        if let Some(path) = &self.board
+        {
+            settings.board = Some(Board { wrap: self.wrap, ..Board::from_ascii(&read_file(path)?) });
+        }
+
+        if let Some(directions) = &self.directions
+        {
+            settings.directions = directions.clone();
+        }
+
+        settings.validate().map_err(|e| e.to_string())?;
+
+        Ok(settings)
+    }
+}
+
+#[derive(Args)]
+struct OutputArgs
+{
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t)]
+    format: Format,
+    /// Show the letters in svg, latex and png output (solution instead of puzzle)
+    #[arg(long)]
+    solution: bool,
+    /// JSON file that maps words to their clues, used by latex output (word#1, word#2, ... for the next copies of a word)
+    #[arg(long)]
+    clues: Option<PathBuf>,
+    /// Output file, standard output if not given.
+    /// When several crosswords are written in svg, latex or png format, the files are numbered (out-1.svg, out-2.svg, ...)
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct GenerateArgs
+{
+    /// Word list file
+    words: PathBuf,
+    /// Maximum number of crosswords to generate
+    #[arg(long, short)]
+    limit: Option<usize>,
+    #[command(flatten)]
+    settings: SettingsArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct RenderArgs
+{
+    /// Crossword file in JSON format
+    input: PathBuf,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct ValidateArgs
+{
+    /// Crossword file in JSON format
+    input: PathBuf,
+    #[command(flatten)]
+    settings: SettingsArgs,
+}
+
+#[derive(Args)]
+struct StatsArgs
+{
+    /// Crossword file in JSON format
+    input: PathBuf,
+}
+
+#[derive(Args)]
+struct WordSearchArgs
+{
+    /// Word list file, a word that is in it several times is placed once
+    words: PathBuf,
+    /// Width of the grid
+    #[arg(long, default_value_t = 12)]
+    width: usize,
+    /// Height of the grid
+    #[arg(long, default_value_t = 12)]
+    height: usize,
+    /// Directions the words can be placed in, separated by commas (all eight if not given)
+    #[arg(long, value_delimiter = ',', value_parser = parse_direction)]
+    directions: Option<Vec<WordDirection>>,
+    /// Don't let words cross each other
+    #[arg(long)]
+    no_overlaps: bool,
+    /// Letters used to fill the cells that are not covered by any word
+    #[arg(long, conflicts_with = "frequencies")]
+    alphabet: Option<String>,
+    /// JSON file that maps letters to their weights, used to fill the cells that are not covered by any word
+    #[arg(long)]
+    frequencies: Option<PathBuf>,
+    /// Seed of the random generator
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Print the answer key instead of the puzzle
+    #[arg(long)]
+    solution: bool,
+    /// Write the puzzle together with its answer key in JSON format
+    #[arg(long)]
+    json: bool,
+    /// Output file, standard output if not given
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct CubeArgs
+{
+    /// Word list file, a word that is in it several times is placed once
+    words: PathBuf,
+    /// Maximum number of crosswords to write
+    #[arg(long, short)]
+    limit: Option<usize>,
+    /// Allow parallel words or words in neighbouring layers to touch side by side
+    #[arg(long)]
+    side_by_side: Option<bool>,
+    /// Allow parallel words to touch head by head
+    #[arg(long)]
+    head_by_head: Option<bool>,
+    /// Allow perpendicular words to touch side by head
+    #[arg(long)]
+    side_by_head: Option<bool>,
+    /// Allow words to touch corner by corner
+    #[arg(long)]
+    corner_by_corner: Option<bool>,
+    /// Maximum size of the crosswords as x,y,z
+    #[arg(long, value_parser = parse_cube_size)]
+    max_size: Option<(usize, usize, usize)>,
+    /// Write the crosswords in JSON format instead of layer by layer
+    #[arg(long)]
+    json: bool,
+    /// Output file, standard output if not given
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+fn parse_direction(direction: &str) -> Result<WordDirection, String>
+{
+    match direction.trim().to_lowercase().as_str()
+    {
+        "right" => Ok(WordDirection::Right),
+        "down" => Ok(WordDirection::Down),
+        "left" => Ok(WordDirection::Left),
+        "up" => Ok(WordDirection::Up),
+        "down-right" => Ok(WordDirection::DownRight),
+        "up-right" => Ok(WordDirection::UpRight),
+        "down-left" => Ok(WordDirection::DownLeft),
+        "up-left" => Ok(WordDirection::UpLeft),
+        _ => Err(format!("unknown direction \"{}\", expected right, down, left, up, down-right, up-right, down-left or up-left", direction))
+    }
+}
+
+fn parse_cube_size(size: &str) -> Result<(usize, usize, usize), String>
+{
+    let values: Vec<usize> = size.split(',').map(|v| v.trim().parse::<usize>()).collect::<Result<_, _>>().map_err(|e| e.to_string())?;
+    match values[..]
+    {
+        [x, y, z] => Ok((x, y, z)),
+        _ => Err(format!("expected three sizes x,y,z, got \"{}\"", size))
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String>
+{
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read_word_list(path: &Path) -> Result<Vec<String>, String>
 {
-    let mut generator = CrosswordGenerator::default();
-    generator.settings = CrosswordGeneratorSettings::default();
-    generator.settings.crossword_settings.size_constraints.push(CrosswordSizeConstraint::MaxLength(13));
-    generator.settings.word_compatibility_settings.side_by_head = true;
-    generator.words = vec!["Hello", "world", "asdf", "myname", "sesame", "yeeee"].into_iter().map(|s| s.to_lowercase()).collect();
-    
-}
\ No newline at end of file
+    Ok(read_file(path)?
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_owned())
+        .collect())
+}
+
+fn parse_crossword<'a>(path: &Path, content: &'a str) -> Result<Crossword<'a>, String>
+{
+    serde_json::from_str(content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn render(cw: &Crossword, args: &OutputArgs, clues: &BTreeMap<String, String>) -> Result<Vec<u8>, String>
+{
+    Ok(match args.format
+    {
+        Format::Text => cw.generate_string().into_bytes(),
+        Format::Json => serde_json::to_string_pretty(cw).map_err(|e| e.to_string())?.into_bytes(),
+        Format::Svg => cw.generate_svg(&SvgSettings { show_letters: args.solution, ..Default::default() }).map_err(|e| e.to_string())?.into_bytes(),
+        Format::Latex => cw.generate_latex(clues, &LatexSettings { variant: if args.solution { LatexVariant::Solution } else { LatexVariant::Puzzle }, ..Default::default() }).into_bytes(),
+        #[cfg(feature = "png")]
+        Format::Png => cw.generate_png(&crossword_generator::raster::ImageSettings { show_letters: args.solution, ..Default::default() }).map_err(|e| e.to_string())?,
+    })
+}
+
+fn write_output(args: &OutputArgs, crosswords: &[Crossword], torus: Option<&Board>) -> Result<(), String>
+{
+    let clues: BTreeMap<String, String> = match &args.clues
+    {
+        Some(path) => serde_json::from_str(&read_file(path)?).map_err(|e| format!("{}: {}", path.display(), e))?,
+        None => BTreeMap::new()
+    };
+
+    let write = |path: Option<&Path>, data: &[u8]| match path
+    {
+        Some(path) => fs::write(path, data).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => std::io::stdout().write_all(data).map_err(|e| e.to_string()),
+    };
+
+    match args.format
+    {
+        Format::Json =>
+        {
+            let data = serde_json::to_string_pretty(crosswords).map_err(|e| e.to_string())? + "\n";
+            write(args.output.as_deref(), data.as_bytes())
+        },
+        Format::Text =>
+        {
+            let data: Vec<String> = crosswords.iter().map(|cw| match torus
+            {
+                Some(board) => board.generate_string(cw),
+                None => cw.generate_string()
+            }).collect();
+            write(args.output.as_deref(), data.join("\n").as_bytes())
+        },
+        _ if torus.is_some() => Err("crosswords on a torus board can only be written as text or json".to_owned()),
+        _ =>
+        {
+            match (&args.output, crosswords.len())
+            {
+                (Some(path), n) if n > 1 =>
+                {
+                    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                    for (ind, cw) in crosswords.iter().enumerate()
+                    {
+                        let numbered = path.with_file_name(format!("{}-{}.{}", stem, ind + 1, args.format.extension()));
+                        write(Some(&numbered), &render(cw, args, &clues)?)?;
+                    }
+                    Ok(())
+                },
+                (output, _) =>
+                {
+                    #[cfg(feature = "png")]
+                    if args.format == Format::Png && output.is_none() && crosswords.len() > 1
+                    {
+                        return Err("several png images can't be written to the standard output, use --output".to_owned());
+                    }
+                    for cw in crosswords
+                    {
+                        write(output.as_deref(), &render(cw, args, &clues)?)?;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+fn generate(args: &GenerateArgs) -> Result<(), String>
+{
+    let generator = CrosswordGenerator { words: read_word_list(&args.words)?, settings: args.settings.load()? };
+    generator.validate().map_err(|e| e.to_string())?;
+
+    let crosswords: Vec<Crossword> = generator.crossword_iter().take(args.limit.unwrap_or(usize::MAX)).collect();
+    if crosswords.is_empty()
+    {
+        eprintln!("no crossword can be generated with the given words and settings");
+    }
+
+    write_output(&args.output, &crosswords, generator.settings.board.as_ref().filter(|board| board.wrap))
+}
+
+fn render_command(args: &RenderArgs) -> Result<(), String>
+{
+    let content = read_file(&args.input)?;
+    let cw = parse_crossword(&args.input, &content)?;
+
+    write_output(&args.output, &[cw], None)
+}
+
+fn validate(args: &ValidateArgs) -> Result<bool, String>
+{
+    let settings = args.settings.load()?;
+    let content = read_file(&args.input)?;
+    let cw = parse_crossword(&args.input, &content)?;
+
+    let mut problems: Vec<String> = settings.get_incompatible_words(&cw).into_iter()
+        .map(|(first, second)| format!("words \"{}\" and \"{}\" are not compatible", first.value, second.value))
+        .collect();
+
+    if !settings.crossword_settings.is_crossword_valid(&cw)
+    {
+        let size = cw.get_size();
+        problems.push(format!("the crossword ({}x{}) does not satisfy the size constraints", size.0, size.1));
+    }
+
+    if !settings.is_crossword_connected(&cw)
+    {
+        problems.push("the words are not all connected".to_owned());
+    }
+
+    for problem in problems.iter()
+    {
+        println!("{}", problem);
+    }
+    if problems.is_empty()
+    {
+        println!("valid");
+    }
+
+    Ok(problems.is_empty())
+}
+
+fn stats(args: &StatsArgs) -> Result<(), String>
+{
+    let content = read_file(&args.input)?;
+    let cw = parse_crossword(&args.input, &content)?;
+
+    let stats = cw.get_stats();
+
+    if stats.diagonal == 0
+    {
+        println!("words: {} ({} across, {} down)", stats.get_word_count(), stats.across, stats.down);
+    }
+    else
+    {
+        println!("words: {} ({} across, {} down, {} diagonal)", stats.get_word_count(), stats.across, stats.down, stats.diagonal);
+    }
+    println!("size: {}x{}", stats.size.0, stats.size.1);
+    println!("letter cells: {}", stats.letter_cells);
+    println!("density: {:.1}%", stats.get_density());
+    println!("intersections: {}", stats.get_intersection_count());
+    println!("average word length: {:.2}", stats.get_average_word_length());
+
+    Ok(())
+}
+
+fn word_search(args: &WordSearchArgs) -> Result<(), String>
+{
+    let words: BTreeSet<String> = read_word_list(&args.words)?.into_iter().collect();
+    let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+
+    let mut settings = WordSearchSettings { width: args.width, height: args.height, allow_overlaps: !args.no_overlaps, seed: args.seed, ..Default::default() };
+    if let Some(directions) = &args.directions
+    {
+        settings.directions = directions.clone();
+    }
+    if let Some(alphabet) = &args.alphabet
+    {
+        settings.letters = LetterSource::Alphabet(alphabet.clone());
+    }
+    if let Some(path) = &args.frequencies
+    {
+        let frequencies = serde_json::from_str(&read_file(path)?).map_err(|e| format!("{}: {}", path.display(), e))?;
+        settings.letters = LetterSource::Frequencies(frequencies);
+    }
+
+    let word_search = WordSearch::generate(&words, &settings).map_err(|e| e.to_string())?;
+    let data = if args.json
+    {
+        serde_json::to_string_pretty(&word_search).map_err(|e| e.to_string())? + "\n"
+    }
+    else
+    {
+        let grid = if args.solution { word_search.generate_answer_key_string() } else { word_search.generate_string() };
+        grid + "\n" + &word_search.get_word_list().join("\n") + "\n"
+    };
+
+    match &args.output
+    {
+        Some(path) => fs::write(path, data).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => std::io::stdout().write_all(data.as_bytes()).map_err(|e| e.to_string()),
+    }
+}
+
+fn cube(args: &CubeArgs) -> Result<(), String>
+{
+    let mut generator = CubeCrosswordGenerator { words: read_word_list(&args.words)?.into_iter().collect(), ..Default::default() };
+    let compatibility = &mut generator.word_compatibility_settings;
+    if let Some(v) = args.side_by_side { compatibility.side_by_side = v; }
+    if let Some(v) = args.head_by_head { compatibility.head_by_head = v; }
+    if let Some(v) = args.side_by_head { compatibility.side_by_head = v; }
+    if let Some(v) = args.corner_by_corner { compatibility.corner_by_corner = v; }
+    generator.max_size = args.max_size;
+
+    let crosswords: Vec<CubeCrossword> = generator.crossword_iter().take(args.limit.unwrap_or(usize::MAX)).collect();
+    if crosswords.is_empty()
+    {
+        eprintln!("no crossword can be generated with the given words and settings");
+    }
+
+    let data = if args.json
+    {
+        serde_json::to_string_pretty(&crosswords).map_err(|e| e.to_string())? + "\n"
+    }
+    else
+    {
+        crosswords.iter().map(|cw| cw.generate_string()).collect::<Vec<_>>().join("\n")
+    };
+
+    match &args.output
+    {
+        Some(path) => fs::write(path, data).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => std::io::stdout().write_all(data.as_bytes()).map_err(|e| e.to_string()),
+    }
+}
+
+fn main() -> ExitCode
+{
+    let cli = Cli::parse();
+
+    let result = match &cli.command
+    {
+        Command::Generate(args) => generate(args).map(|_| true),
+        Command::Render(args) => render_command(args).map(|_| true),
+        Command::Validate(args) => validate(args),
+        Command::Stats(args) => stats(args).map(|_| true),
+        Command::WordSearch(args) => word_search(args).map(|_| true),
+        Command::Cube(args) => cube(args).map(|_| true),
+    };
+
+    match result
+    {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) =>
+        {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
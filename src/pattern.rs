@@ -0,0 +1,309 @@
+use std::collections::VecDeque;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Serialize, Deserialize};
+
+use super::fill::*;
+
+
+/// Represents the symmetry of a block pattern
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub enum Symmetry
+{
+    /// The pattern looks the same after a rotation by 180 degrees, the usual newspaper rule
+    #[default]
+    Rotational,
+    /// The pattern looks the same after a reflection over its vertical axis
+    Mirror,
+    /// Blocks are placed independently
+    None
+}
+
+/// Represents settings for [generating block patterns](GridTemplate::generate_block_pattern)
+///
+/// ## Fields
+///
+/// width, height -> size of the pattern
+///
+/// block_percentage -> percentage of cells that should become blocks, from 0 to 100 (newspaper grids have about 16)
+///
+/// symmetry -> [Symmetry] of the blocks
+///
+/// min_slot_length -> minimal length of every horizontal and vertical run of open cells, so every letter belongs to two [slots](Slot) of at least this length
+///
+/// seed -> seed of the random generator, the same settings always give the same pattern
+///
+/// max_attempts -> how many times the generation is restarted before giving up with [BlockPatternError::NotFound]
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlockPatternSettings
+{
+    pub width: usize,
+    pub height: usize,
+    pub block_percentage: u32,
+    pub symmetry: Symmetry,
+    pub min_slot_length: usize,
+    pub seed: u64,
+    pub max_attempts: usize
+}
+
+impl Default for BlockPatternSettings
+{
+    fn default() -> Self
+    {
+        BlockPatternSettings
+        {
+            width: 15,
+            height: 15,
+            block_percentage: 16,
+            symmetry: Symmetry::Rotational,
+            min_slot_length: 3,
+            seed: 0,
+            max_attempts: 100
+        }
+    }
+}
+
+/// Represents an error of [generating block patterns](GridTemplate::generate_block_pattern)
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum BlockPatternError
+{
+    /// The width or height is smaller than the [minimal slot length](BlockPatternSettings::min_slot_length)
+    TooSmall,
+    /// The [block percentage](BlockPatternSettings::block_percentage) is bigger than 100
+    InvalidBlockPercentage(u32),
+    /// No valid pattern with enough blocks was found in [max_attempts](BlockPatternSettings::max_attempts) attempts
+    NotFound
+}
+
+impl std::fmt::Display for BlockPatternError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            BlockPatternError::TooSmall => write!(f, "the pattern is smaller than the minimal slot length"),
+            BlockPatternError::InvalidBlockPercentage(p) => write!(f, "the block percentage {} is bigger than 100", p),
+            BlockPatternError::NotFound => write!(f, "no valid block pattern was found, try a smaller block percentage or more attempts"),
+        }
+    }
+}
+
+impl std::error::Error for BlockPatternError {}
+
+/// Returns the cells that become blocks together with (x, y) to keep the symmetry
+fn symmetric_cells(x: usize, y: usize, width: usize, height: usize, symmetry: Symmetry) -> Vec<(usize, usize)>
+{
+    let other = match symmetry
+    {
+        Symmetry::Rotational => (width - 1 - x, height - 1 - y),
+        Symmetry::Mirror => (width - 1 - x, y),
+        Symmetry::None => (x, y)
+    };
+
+    if other == (x, y) { vec![(x, y)] } else { vec![(x, y), other] }
+}
+
+/// Checks that every run of open cells is at least min_slot_length long and that all open cells are connected
+fn is_pattern_valid(blocks: &[Vec<bool>], min_slot_length: usize) -> bool
+{
+    let height = blocks.len();
+    let width = blocks[0].len();
+
+    let is_block = |horizontal: bool, o: usize, i: usize| if horizontal { blocks[o][i] } else { blocks[i][o] };
+    for (horizontal, outer, inner) in [(true, height, width), (false, width, height)]
+    {
+        for o in 0..outer
+        {
+            let mut run = 0;
+            for i in 0..=inner
+            {
+                let open = i < inner && !is_block(horizontal, o, i);
+                if open
+                {
+                    run += 1;
+                    continue;
+                }
+                if run != 0 && run < min_slot_length { return false; }
+                run = 0;
+            }
+        }
+    }
+
+    let open_cells = blocks.iter().flatten().filter(|b| !**b).count();
+    let start = match (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).find(|(x, y)| !blocks[*y][*x])
+    {
+        Some(start) => start,
+        None => return false
+    };
+
+    let mut visited = vec![vec![false; width]; height];
+    let mut queue = VecDeque::from([start]);
+    visited[start.1][start.0] = true;
+    let mut reached = 0;
+    while let Some((x, y)) = queue.pop_front()
+    {
+        reached += 1;
+        let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+        for (nx, ny) in neighbours
+        {
+            if nx < width && ny < height && !blocks[ny][nx] && !visited[ny][nx]
+            {
+                visited[ny][nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    reached == open_cells
+}
+
+impl GridTemplate
+{
+    /// Generates a random block pattern, a [template](GridTemplate) without given letters that can be [filled](GridTemplate::fill)
+    ///
+    /// Blocks are added at random cells (with their symmetric counterparts) as long as every run of open cells stays at least
+    /// [min_slot_length](BlockPatternSettings::min_slot_length) long and the open cells stay connected, until the [block percentage](BlockPatternSettings::block_percentage) is reached.
+    /// If it can't be reached, the generation starts again.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::fill::{GridTemplate, TemplateCell};
+    /// # use crossword_generator::pattern::{BlockPatternSettings, Symmetry};
+    /// let template = GridTemplate::generate_block_pattern(&BlockPatternSettings { seed: 7, ..Default::default() }).unwrap();
+    ///
+    /// assert_eq!(template.get_size(), (15, 15));
+    /// let table = template.generate_char_table();
+    /// for y in 0..15
+    /// {
+    ///     for x in 0..15
+    ///     {
+    ///         assert_eq!(table[y][x], table[14 - y][14 - x]);
+    ///     }
+    /// }
+    /// assert!(template.get_slots().iter().all(|slot| slot.length >= 3));
+    /// ```
+    pub fn generate_block_pattern(settings: &BlockPatternSettings) -> Result<GridTemplate, BlockPatternError>
+    {
+        let (width, height) = (settings.width, settings.height);
+        if width == 0 || height == 0 || width < settings.min_slot_length || height < settings.min_slot_length
+        {
+            return Err(BlockPatternError::TooSmall);
+        }
+        if settings.block_percentage > 100
+        {
+            return Err(BlockPatternError::InvalidBlockPercentage(settings.block_percentage));
+        }
+
+        let target = width * height * settings.block_percentage as usize / 100;
+        let mut rng = StdRng::seed_from_u64(settings.seed);
+        let mut cells: Vec<(usize, usize)> = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).collect();
+
+        for _ in 0..settings.max_attempts.max(1)
+        {
+            let mut blocks = vec![vec![false; width]; height];
+            let mut block_count = 0;
+            cells.shuffle(&mut rng);
+
+            for (x, y) in cells.iter()
+            {
+                if block_count >= target { break; }
+                if blocks[*y][*x] { continue; }
+
+                let group = symmetric_cells(*x, *y, width, height, settings.symmetry);
+
+                group.iter().for_each(|(gx, gy)| blocks[*gy][*gx] = true);
+                if is_pattern_valid(&blocks, settings.min_slot_length)
+                {
+                    block_count += group.len();
+                }
+                else
+                {
+                    group.iter().for_each(|(gx, gy)| blocks[*gy][*gx] = false);
+                }
+            }
+
+            if block_count >= target
+            {
+                let mut template = GridTemplate::new(width, height);
+                for (y, row) in blocks.iter().enumerate()
+                {
+                    for (x, block) in row.iter().enumerate()
+                    {
+                        if *block { template.set_cell(x, y, TemplateCell::Block); }
+                    }
+                }
+                return Ok(template);
+            }
+        }
+
+        Err(BlockPatternError::NotFound)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    fn blocks_of(template: &GridTemplate) -> Vec<Vec<bool>>
+    {
+        template.generate_char_table().into_iter().map(|row| row.into_iter().map(|ch| ch == '#').collect()).collect()
+    }
+
+    #[test]
+    fn test_generate_block_pattern() {
+        for symmetry in [Symmetry::Rotational, Symmetry::Mirror, Symmetry::None]
+        {
+            for seed in 0..5
+            {
+                let settings = BlockPatternSettings { width: 13, height: 11, symmetry, seed, ..Default::default() };
+                let template = GridTemplate::generate_block_pattern(&settings).unwrap();
+                let blocks = blocks_of(&template);
+
+                assert_eq!(template.get_size(), (13, 11));
+                assert!(blocks.iter().flatten().filter(|b| **b).count() >= 13 * 11 * 16 / 100);
+                assert!(is_pattern_valid(&blocks, 3));
+                for y in 0..11
+                {
+                    for x in 0..13
+                    {
+                        match symmetry
+                        {
+                            Symmetry::Rotational => assert_eq!(blocks[y][x], blocks[10 - y][12 - x]),
+                            Symmetry::Mirror => assert_eq!(blocks[y][x], blocks[y][12 - x]),
+                            Symmetry::None => ()
+                        }
+                    }
+                }
+
+                assert_eq!(GridTemplate::generate_block_pattern(&settings), Ok(template));
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_block_pattern_errors() {
+        assert_eq!(GridTemplate::generate_block_pattern(&BlockPatternSettings { width: 2, ..Default::default() }), Err(BlockPatternError::TooSmall));
+        assert_eq!(GridTemplate::generate_block_pattern(&BlockPatternSettings { block_percentage: 101, ..Default::default() }), Err(BlockPatternError::InvalidBlockPercentage(101)));
+        assert_eq!(GridTemplate::generate_block_pattern(&BlockPatternSettings { width: 3, height: 3, block_percentage: 50, ..Default::default() }), Err(BlockPatternError::NotFound));
+        assert_eq!(GridTemplate::generate_block_pattern(&BlockPatternSettings { width: 3, height: 3, block_percentage: 0, ..Default::default() }), Ok(GridTemplate::new(3, 3)));
+    }
+
+    #[test]
+    fn test_is_pattern_valid() {
+        let t = true;
+        let f = false;
+        assert!(is_pattern_valid(&[vec![f, f, f], vec![f, f, f], vec![f, f, f]], 3));
+        assert!(!is_pattern_valid(&[vec![t, f, f], vec![f, f, f], vec![f, f, f]], 3));
+        assert!(is_pattern_valid(&[vec![t, f, f], vec![f, f, f], vec![f, f, f]], 2));
+        assert!(!is_pattern_valid(&[vec![f, f, f], vec![t, t, t], vec![f, f, f]], 3));
+        assert!(!is_pattern_valid(&[vec![t, t], vec![t, t]], 1));
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+
+use super::crossword::*;
+
+
+/// Width of a glyph of the built-in font in pixels
+const GLYPH_WIDTH: u32 = 5;
+/// Height of a glyph of the built-in font in pixels
+const GLYPH_HEIGHT: u32 = 7;
+/// Largest number of pixels of an image, one byte each, larger images are [too large](ImageError::TooLarge) instead of failing to allocate
+pub const MAX_PIXELS: usize = 100_000_000;
+
+/// Returns the rows of the built-in 5x7 bitmap glyph of the character (the lowest 5 bits of every row are used, the highest of them is the leftmost pixel)
+///
+/// Only digits and latin letters have glyphs, all other characters are drawn as a question mark.
+fn glyph(ch: char) -> [u8; 7]
+{
+    match ch.to_ascii_uppercase()
+    {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        _   => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+    }
+}
+
+/// Represents settings for rendering a [crossword](Crossword) as a raster image with [generate_image](Crossword::generate_image)
+///
+/// ## Fields
+///
+/// cell_size -> width and height of a single cell in pixels
+///
+/// margin -> size of the white border around the grid in pixels
+///
+/// show_letters -> if true, the letters of the words are drawn in the cells (solution), otherwise the cells are left empty (puzzle)
+///
+/// show_numbers -> if true, the clue numbers (see [get_numbered_words](Crossword::get_numbered_words)) are drawn in the top left corners of the cells
+///
+/// empty_cells_as_blocks -> if true, cells without letters are filled with black, otherwise they are left white
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct ImageSettings
+{
+    pub cell_size: u32,
+    pub margin: u32,
+    pub show_letters: bool,
+    pub show_numbers: bool,
+    pub empty_cells_as_blocks: bool
+}
+
+impl Default for ImageSettings
+{
+    fn default() -> Self
+    {
+        ImageSettings
+        {
+            cell_size: 40,
+            margin: 10,
+            show_letters: false,
+            show_numbers: true,
+            empty_cells_as_blocks: false
+        }
+    }
+}
+
+/// Represents an error of rendering a [crossword](Crossword) as a raster image
+#[derive(Debug)]
+pub enum ImageError
+{
+    /// The image would be too large, its width or height doesn't fit into the integer types used or it has more than [MAX_PIXELS] pixels
+    TooLarge,
+    /// The image couldn't be encoded as PNG
+    Encoding(::png::EncodingError)
+}
+
+impl std::fmt::Display for ImageError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            ImageError::TooLarge => write!(f, "the image is too large, use a smaller cell size or margin"),
+            ImageError::Encoding(e) => write!(f, "png encoding failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<::png::EncodingError> for ImageError
+{
+    fn from(e: ::png::EncodingError) -> Self
+    {
+        ImageError::Encoding(e)
+    }
+}
+
+/// Represents an 8 bit grayscale raster image, rows are stored one after another from top to bottom
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug)]
+pub struct GrayImage
+{
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>
+}
+
+impl GrayImage
+{
+    const WHITE: u8 = 255;
+    const BLACK: u8 = 0;
+
+    fn new(width: u32, height: u32) -> Result<GrayImage, ImageError>
+    {
+        let pixel_count = (width as usize).checked_mul(height as usize).filter(|count| *count <= MAX_PIXELS).ok_or(ImageError::TooLarge)?;
+        Ok(GrayImage { width, height, pixels: vec![GrayImage::WHITE; pixel_count] })
+    }
+
+    fn get_index(&self, x: u32, y: u32) -> usize
+    {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Returns the value of the pixel, or None if the coordinates are outside of the image
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<u8>
+    {
+        if x >= self.width || y >= self.height { return None; }
+        Some(self.pixels[self.get_index(x, y)])
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, w: u32, h: u32, value: u8)
+    {
+        for py in y..y.saturating_add(h).min(self.height)
+        {
+            for px in x..x.saturating_add(w).min(self.width)
+            {
+                let index = self.get_index(px, py);
+                self.pixels[index] = value;
+            }
+        }
+    }
+
+    fn draw_glyph(&mut self, ch: char, x: u32, y: u32, scale: u32)
+    {
+        for (row_ind, row) in glyph(ch).iter().enumerate()
+        {
+            for col_ind in 0..GLYPH_WIDTH
+            {
+                if row & (1 << (GLYPH_WIDTH - 1 - col_ind)) != 0
+                {
+                    self.fill_rect(x + col_ind * scale, y + row_ind as u32 * scale, scale, scale, GrayImage::BLACK);
+                }
+            }
+        }
+    }
+
+    fn draw_text(&mut self, text: &str, x: u32, y: u32, scale: u32)
+    {
+        for (ind, ch) in text.chars().enumerate()
+        {
+            self.draw_glyph(ch, x + ind as u32 * (GLYPH_WIDTH + 1) * scale, y, scale);
+        }
+    }
+
+    /// Encodes the image as PNG
+    pub fn encode_png(&self) -> Result<Vec<u8>, ::png::EncodingError>
+    {
+        let mut result = vec![];
+        {
+            let mut encoder = ::png::Encoder::new(&mut result, self.width, self.height);
+            encoder.set_color(::png::ColorType::Grayscale);
+            encoder.set_depth(::png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.pixels)?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl<'a> Crossword<'a>
+{
+    /// Returns a grayscale raster image of the [crossword](Crossword)
+    ///
+    /// The grid is built with [generate_char_table](Crossword::generate_char_table), letters and numbers are drawn with a built-in bitmap font,
+    /// so no system fonts are needed. Characters that the font does not have are drawn as question marks.
+    /// Clue numbers are drawn in a band at the top of the cell and letters below it, the number font gets smaller (and the cells larger if needed) so that numbers with more digits fit.
+    ///
+    /// Returns [ImageError::TooLarge] if the size of the image in pixels overflows or the image has more than [MAX_PIXELS] pixels.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// # use crossword_generator::raster::ImageSettings;
+    /// let cw = Crossword::new(&[                                                                                 //     -----
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},     //    |c a t|
+    ///     Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},      //    |    o|
+    /// ]);                                                                                                        //    |    y|
+    ///                                                                                                            //     -----
+    /// let image = cw.generate_image(&ImageSettings { cell_size: 30, margin: 5, ..Default::default() }).unwrap();
+    ///
+    /// assert_eq!((image.width, image.height), (3 * 30 + 1 + 2 * 5, 3 * 30 + 1 + 2 * 5));
+    /// ```
+    pub fn generate_image(&self, settings: &ImageSettings) -> Result<GrayImage, ImageError>
+    {
+        let table = self.generate_char_table();
+        let size = self.get_size();
+        let numbers: BTreeMap<(usize, usize), usize> = match settings.show_numbers
+        {
+            true => self.get_cell_numbers(),
+            false => BTreeMap::new()
+        };
+
+        let max_digits = numbers.values().max().map(|n| n.to_string().len() as u32).unwrap_or(0);
+        let number_width = |scale: u32| max_digits * (GLYPH_WIDTH + 1) * scale + 2 * scale;
+        let min_cell = match max_digits
+        {
+            0 => GLYPH_HEIGHT + 2,
+            _ => number_width(1).max(2 * GLYPH_HEIGHT + 4)
+        };
+        let cell = settings.cell_size.max(min_cell);
+
+        let image_side = |cells: usize| -> Option<u32>
+        {
+            u32::try_from(cells).ok()?.checked_mul(cell)?.checked_add(1)?.checked_add(settings.margin.checked_mul(2)?)
+        };
+        let width = image_side(size.0).ok_or(ImageError::TooLarge)?;
+        let height = image_side(size.1).ok_or(ImageError::TooLarge)?;
+        let mut image = GrayImage::new(width, height)?;
+
+        let mut number_scale = (cell / 20).max(1);
+        while number_scale > 1 && number_width(number_scale) > cell
+        {
+            number_scale -= 1;
+        }
+        let number_band = match max_digits
+        {
+            0 => 0,
+            _ => (GLYPH_HEIGHT + 2) * number_scale
+        };
+        let letter_scale = ((cell - number_band) / (GLYPH_HEIGHT + 2)).min(cell / 2 / GLYPH_HEIGHT).max(1);
+
+        for (y, row) in table.iter().enumerate()
+        {
+            for (x, ch) in row.iter().enumerate()
+            {
+                let cell_x = settings.margin + x as u32 * cell;
+                let cell_y = settings.margin + y as u32 * cell;
+
+                if *ch == ' '
+                {
+                    if settings.empty_cells_as_blocks
+                    {
+                        image.fill_rect(cell_x, cell_y, cell + 1, cell + 1, GrayImage::BLACK);
+                    }
+                    continue;
+                }
+
+                image.fill_rect(cell_x, cell_y, cell + 1, 1, GrayImage::BLACK);
+                image.fill_rect(cell_x, cell_y + cell, cell + 1, 1, GrayImage::BLACK);
+                image.fill_rect(cell_x, cell_y, 1, cell + 1, GrayImage::BLACK);
+                image.fill_rect(cell_x + cell, cell_y, 1, cell + 1, GrayImage::BLACK);
+
+                if let Some(n) = numbers.get(&(x, y))
+                {
+                    image.draw_text(&n.to_string(), cell_x + 2 * number_scale, cell_y + 2 * number_scale, number_scale);
+                }
+
+                if settings.show_letters
+                {
+                    let letter_x = cell_x + (cell + 1 - GLYPH_WIDTH * letter_scale) / 2;
+                    let letter_y = cell_y + number_band + (cell + 1 - number_band - GLYPH_HEIGHT * letter_scale) / 2;
+                    image.draw_glyph(*ch, letter_x, letter_y, letter_scale);
+                }
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Returns the [crossword](Crossword) rendered with [generate_image](Crossword::generate_image) and encoded as PNG
+    pub fn generate_png(&self, settings: &ImageSettings) -> Result<Vec<u8>, ImageError>
+    {
+        Ok(self.generate_image(settings)?.encode_png()?)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+    use crate::word::*;
+
+    fn test_crossword() -> Crossword<'static>
+    {
+        Crossword::new(
+            &[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},
+            ])
+    }
+
+    #[test]
+    fn test_crossword_generate_image() {
+        let cw = test_crossword();
+        let settings = ImageSettings { cell_size: 20, margin: 0, show_letters: true, show_numbers: false, empty_cells_as_blocks: true };
+        let image = cw.generate_image(&settings).unwrap();
+
+        assert_eq!((image.width, image.height), (61, 61));
+
+        // grid lines of the first cell
+        assert_eq!(image.get_pixel(0, 0), Some(0));
+        assert_eq!(image.get_pixel(20, 10), Some(0));
+        // the empty cell under 'c' is a block
+        assert_eq!(image.get_pixel(10, 30), Some(0));
+        // inside of the 'c' cell there are both black (letter) and white pixels
+        let c_cell: Vec<u8> = (1..20).flat_map(|y| (1..20).map(move |x| (x, y))).map(|(x, y)| image.get_pixel(x, y).unwrap()).collect();
+        assert!(c_cell.contains(&0) && c_cell.contains(&255));
+
+        let empty_settings = ImageSettings { show_letters: false, ..settings };
+        let empty_image = cw.generate_image(&empty_settings).unwrap();
+        assert!((1..20).all(|y| (1..20).all(|x| empty_image.get_pixel(x, y) == Some(255))));
+    }
+
+    #[test]
+    fn test_two_digit_numbers() {
+        let words: Vec<Word> = (0..10).map(|y| Word{position: WordPosition { x: 0, y: 2 * y }, direction: WordDirection::Right, value: "ab", id: 0}).collect();
+        let cw = Crossword::new(&words);
+
+        for cell_size in [10, 20, 40, 100]
+        {
+            let settings = ImageSettings { cell_size, margin: 0, show_letters: false, show_numbers: true, empty_cells_as_blocks: false };
+            let numbers = cw.generate_image(&settings).unwrap();
+            let both = cw.generate_image(&ImageSettings { show_letters: true, ..settings }).unwrap();
+
+            // in the cell of the word number 10, the number is above the letter and stays inside the cell
+            let cell = numbers.width / 2;
+            let top = 18 * cell;
+            let rows = |is_black: &dyn Fn(u32, u32) -> bool| (top + 1..top + cell).filter(|y| (1..cell).any(|x| is_black(x, *y))).collect::<Vec<u32>>();
+            let number_rows = rows(&|x, y| numbers.get_pixel(x, y) == Some(0));
+            let letter_rows = rows(&|x, y| both.get_pixel(x, y) == Some(0) && numbers.get_pixel(x, y) == Some(255));
+            assert!(!number_rows.is_empty() && !letter_rows.is_empty());
+            assert!(number_rows.last() < letter_rows.first());
+            assert!((top + 1..top + cell).all(|y| (cell + 1..2 * cell).all(|x| numbers.get_pixel(x, y) == Some(255))));
+        }
+    }
+
+    #[test]
+    fn test_too_large_image() {
+        let cw = test_crossword();
+        assert!(matches!(cw.generate_image(&ImageSettings { cell_size: u32::MAX / 2, ..Default::default() }), Err(ImageError::TooLarge)));
+        assert!(matches!(cw.generate_png(&ImageSettings { margin: u32::MAX, ..Default::default() }), Err(ImageError::TooLarge)));
+        assert!(matches!(cw.generate_image(&ImageSettings { cell_size: 100_000, ..Default::default() }), Err(ImageError::TooLarge)));
+        assert!(cw.generate_image(&ImageSettings { cell_size: 1000, margin: 0, ..Default::default() }).is_ok());
+    }
+
+    #[test]
+    fn test_crossword_generate_png() {
+        let cw = test_crossword();
+        let png = cw.generate_png(&ImageSettings::default()).unwrap();
+
+        let decoder = ::png::Decoder::new(png.as_slice());
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (3 * 40 + 1 + 20, 3 * 40 + 1 + 20));
+        assert_eq!(info.color_type, ::png::ColorType::Grayscale);
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Serialize, Deserialize};
+
+use super::crossword::*;
+use super::hex::*;
+
+
+/// Represents settings for rendering a [crossword](Crossword) as SVG with [generate_svg](Crossword::generate_svg)
+///
+/// ## Fields
+///
+/// cell_size -> width and height of a single cell in SVG user units
+///
+/// margin -> size of the empty border around the grid
+///
+/// show_letters -> if true, the letters of the words are drawn in the cells (solution), otherwise the cells are left empty (puzzle)
+///
+/// show_numbers -> if true, the clue numbers (see [get_numbered_words](Crossword::get_numbered_words)) are drawn in the top left corners of the cells
+///
+/// empty_cells_as_blocks -> if true, cells without letters are filled with black, otherwise they are not drawn
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct SvgSettings
+{
+    pub cell_size: u32,
+    pub margin: u32,
+    pub show_letters: bool,
+    pub show_numbers: bool,
+    pub empty_cells_as_blocks: bool
+}
+
+impl Default for SvgSettings
+{
+    fn default() -> Self
+    {
+        SvgSettings
+        {
+            cell_size: 40,
+            margin: 10,
+            show_letters: false,
+            show_numbers: true,
+            empty_cells_as_blocks: false
+        }
+    }
+}
+
+/// Represents an error of rendering a [crossword](Crossword) as SVG
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug)]
+pub enum SvgError
+{
+    /// The width or height of the document doesn't fit into [u32]
+    TooLarge
+}
+
+impl std::fmt::Display for SvgError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            SvgError::TooLarge => write!(f, "the svg document is too large, use a smaller cell size or margin"),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+/// Escapes the characters that have a special meaning in XML
+fn escape_xml(text: &str) -> String
+{
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars()
+    {
+        match ch
+        {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _ => result.push(ch)
+        }
+    }
+
+    result
+}
+
+impl<'a> Crossword<'a>
+{
+    /// Returns an SVG document that draws the [crossword](Crossword)
+    ///
+    /// The grid is built with [generate_char_table](Crossword::generate_char_table), every cell with a letter is drawn as a square.
+    ///
+    /// Returns [SvgError::TooLarge] if the width or height of the document overflows.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// # use crossword_generator::crossword::Crossword;
+    /// # use crossword_generator::svg::SvgSettings;
+    /// let cw = Crossword::new(&[                                                                                 //     ---
+    ///     Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "a", id: 0},       //    |a|
+    /// ]);                                                                                                        //     ---
+    ///
+    /// assert_eq!(cw.generate_svg(&SvgSettings { cell_size: 20, margin: 0, show_letters: true, show_numbers: true, empty_cells_as_blocks: false }).unwrap(),
+    /// "\
+    /// <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"20\" viewBox=\"0 0 20 20\">
+    /// <rect x=\"0\" y=\"0\" width=\"20\" height=\"20\" fill=\"white\" stroke=\"black\" stroke-width=\"1\"/>
+    /// <text x=\"1\" y=\"6\" font-family=\"sans-serif\" font-size=\"6\">1</text>
+    /// <text x=\"10\" y=\"11\" font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"middle\" dominant-baseline=\"middle\">A</text>
+    /// </svg>
+    /// ");
+    /// ```
+    pub fn generate_svg(&self, settings: &SvgSettings) -> Result<String, SvgError>
+    {
+        let table = self.generate_char_table();
+        let size = self.get_size();
+        let numbers = self.get_cell_numbers();
+
+        let document_side = |cells: usize| -> Option<u32>
+        {
+            u32::try_from(cells).ok()?.checked_mul(settings.cell_size)?.checked_add(settings.margin.checked_mul(2)?)
+        };
+        let width = document_side(size.0).ok_or(SvgError::TooLarge)?;
+        let height = document_side(size.1).ok_or(SvgError::TooLarge)?;
+
+        // the coordinates inside the cells fit into u32 as well, but intermediate products like cell * 11 might not
+        let (cell, margin) = (u64::from(settings.cell_size), u64::from(settings.margin));
+
+        let mut result = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n", width, height);
+
+        for (y, row) in table.iter().enumerate()
+        {
+            for (x, ch) in row.iter().enumerate()
+            {
+                let cell_x = margin + x as u64 * cell;
+                let cell_y = margin + y as u64 * cell;
+
+                if *ch == ' '
+                {
+                    if settings.empty_cells_as_blocks
+                    {
+                        result.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"black\"/>\n", cell_x, cell_y, cell));
+                    }
+                    continue;
+                }
+
+                result.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"white\" stroke=\"black\" stroke-width=\"1\"/>\n", cell_x, cell_y, cell));
+
+                if settings.show_numbers
+                {
+                    if let Some(n) = numbers.get(&(x, y))
+                    {
+                        let font_size = cell * 3 / 10;
+                        result.push_str(&format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\">{}</text>\n", cell_x + cell / 20, cell_y + font_size, font_size, n));
+                    }
+                }
+
+                if settings.show_letters
+                {
+                    result.push_str(&format!("<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+                        cell_x + cell / 2, cell_y + cell * 11 / 20, cell / 2, escape_xml(&ch.to_uppercase().to_string())));
+                }
+            }
+        }
+
+        result.push_str("</svg>\n");
+
+        Ok(result)
+    }
+}
+
+impl<'a> HexCrossword<'a>
+{
+    /// Returns an SVG document that draws the [hex crossword](HexCrossword) with pointy-topped hexagons
+    ///
+    /// [cell_size](SvgSettings::cell_size) is the width of a hexagon, [empty_cells_as_blocks](SvgSettings::empty_cells_as_blocks) fills the empty cells
+    /// of every row between its first and last letter. Coordinates are written with one decimal place.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::hex::{HexCrossword, HexDirection, HexPosition, HexWord};
+    /// # use crossword_generator::svg::SvgSettings;
+    /// let cw = HexCrossword::new(&[
+    ///     HexWord{ position: HexPosition{ q: 0, r: 0 }, direction: HexDirection::East, value: "a" },
+    /// ]);
+    ///
+    /// assert_eq!(cw.generate_svg(&SvgSettings { cell_size: 20, margin: 0, show_letters: true, show_numbers: false, empty_cells_as_blocks: false }),
+    /// "\
+    /// <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20.0\" height=\"23.1\" viewBox=\"0 0 20.0 23.1\">
+    /// <polygon points=\"10.0,0.0 20.0,5.8 20.0,17.3 10.0,23.1 0.0,17.3 0.0,5.8\" fill=\"white\" stroke=\"black\" stroke-width=\"1\"/>
+    /// <text x=\"10.0\" y=\"12.5\" font-family=\"sans-serif\" font-size=\"10\" text-anchor=\"middle\" dominant-baseline=\"middle\">A</text>
+    /// </svg>
+    /// ");
+    /// ```
+    pub fn generate_svg(&self, settings: &SvgSettings) -> String
+    {
+        let letters = self.get_letters();
+        let numbers: BTreeMap<HexPosition, usize> = self.get_numbered_words().iter().map(|(n, w)| (w.position.clone(), *n)).collect();
+
+        let width = settings.cell_size as f64;
+        let radius = width / 3f64.sqrt();
+        let center = |position: &HexPosition| (width * (position.q as f64 + position.r as f64 / 2.0), 1.5 * radius * position.r as f64);
+
+        let mut cells: Vec<(HexPosition, Option<char>)> = vec![];
+        let rows: BTreeSet<isize> = letters.keys().map(|p| p.r).collect();
+        for r in rows
+        {
+            let row: Vec<isize> = letters.keys().filter(|p| p.r == r).map(|p| p.q).collect();
+            let (first, last) = (row.iter().min().copied().unwrap_or(0), row.iter().max().copied().unwrap_or(0));
+            for q in first..=last
+            {
+                let position = HexPosition { q, r };
+                let letter = letters.get(&position).copied();
+                if letter.is_some() || settings.empty_cells_as_blocks
+                {
+                    cells.push((position, letter));
+                }
+            }
+        }
+
+        let min_x = cells.iter().map(|(p, _)| center(p).0).fold(f64::INFINITY, f64::min);
+        let max_x = cells.iter().map(|(p, _)| center(p).0).fold(f64::NEG_INFINITY, f64::max);
+        let max_y = cells.iter().map(|(p, _)| center(p).1).fold(f64::NEG_INFINITY, f64::max);
+        let margin = settings.margin as f64;
+        let (offset_x, offset_y) = (margin + width / 2.0 - min_x, margin + radius);
+        let (svg_width, svg_height) = if cells.is_empty() { (2.0 * margin, 2.0 * margin) } else { (max_x - min_x + width + 2.0 * margin, max_y + 2.0 * radius + 2.0 * margin) };
+
+        let mut result = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.1}\" height=\"{1:.1}\" viewBox=\"0 0 {0:.1} {1:.1}\">\n", svg_width, svg_height);
+
+        for (position, letter) in cells.iter()
+        {
+            let (x, y) = center(position);
+            let (x, y) = (x + offset_x, y + offset_y);
+            let points: Vec<String> = [(0.0, -radius), (width / 2.0, -radius / 2.0), (width / 2.0, radius / 2.0), (0.0, radius), (-width / 2.0, radius / 2.0), (-width / 2.0, -radius / 2.0)]
+                .iter()
+                .map(|(dx, dy)| format!("{:.1},{:.1}", x + dx, y + dy))
+                .collect();
+            let fill = if letter.is_some() { "white" } else { "black" };
+            result.push_str(&format!("<polygon points=\"{}\" fill=\"{}\" stroke=\"black\" stroke-width=\"1\"/>\n", points.join(" "), fill));
+
+            let Some(ch) = letter else { continue; };
+            if settings.show_numbers
+            {
+                if let Some(n) = numbers.get(position)
+                {
+                    let font_size = settings.cell_size * 3 / 10;
+                    result.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\">{}</text>\n", x, y - radius / 2.0, font_size, n));
+                }
+            }
+
+            if settings.show_letters
+            {
+                result.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+                    x, y + width / 20.0, settings.cell_size / 2, escape_xml(&ch.to_uppercase().to_string())));
+            }
+        }
+
+        result.push_str("</svg>\n");
+
+        result
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+    use crate::word::*;
+
+    #[test]
+    fn test_crossword_generate_svg() {
+        let cw = Crossword::new(
+            &[
+                Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0},
+                Word{position: WordPosition { x: 2, y: 0 }, direction: WordDirection::Down, value: "toy", id: 0},
+            ]);
+
+        let svg = cw.generate_svg(&SvgSettings { empty_cells_as_blocks: true, ..Default::default() }).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"140\" height=\"140\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("fill=\"white\"").count(), 5);
+        assert_eq!(svg.matches("fill=\"black\"").count(), 4);
+        assert_eq!(svg.matches("<text").count(), 2);
+        assert!(!svg.contains(">C</text>"));
+
+        assert_eq!(cw.generate_svg(&SvgSettings { cell_size: u32::MAX / 2, ..Default::default() }), Err(SvgError::TooLarge));
+        assert_eq!(cw.generate_svg(&SvgSettings { margin: u32::MAX, ..Default::default() }), Err(SvgError::TooLarge));
+        assert!(cw.generate_svg(&SvgSettings { cell_size: u32::MAX / 4, margin: 0, ..Default::default() }).is_ok());
+    }
+
+    #[test]
+    fn test_hex_crossword_generate_svg() {
+        let cw = HexCrossword::new(
+            &[
+                HexWord{ position: HexPosition{ q: 0, r: 0 }, direction: HexDirection::East, value: "hello" },
+                HexWord{ position: HexPosition{ q: 2, r: 0 }, direction: HexDirection::SouthWest, value: "lid" },
+            ]);
+
+        let svg = cw.generate_svg(&SvgSettings { show_letters: true, ..Default::default() });
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"220.0\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polygon").count(), 7);
+        assert_eq!(svg.matches(">1</text>").count(), 1);
+        assert_eq!(svg.matches(">2</text>").count(), 1);
+        assert!(svg.contains(">D</text>"));
+
+        let mut cw = cw;
+        cw.add_word(&HexWord{ position: HexPosition{ q: 4, r: 0 }, direction: HexDirection::SouthWest, value: "oak" });
+        let blocks = cw.generate_svg(&SvgSettings { empty_cells_as_blocks: true, ..Default::default() });
+        assert_eq!(blocks.matches("fill=\"black\"").count(), 2);
+        assert_eq!(blocks.matches("<polygon").count(), 11);
+        assert_eq!(HexCrossword::default().generate_svg(&SvgSettings::default()).matches("<polygon").count(), 0);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a & 'b'>\""), "&lt;a &amp; &apos;b&apos;&gt;&quot;");
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::BTreeSet;
+use std::sync::Arc;
 use itertools::Itertools;
 
 use serde::{Serialize, Deserialize};
@@ -57,7 +58,7 @@ impl WordCompatibilitySettings
     {
         if first.corners_touch(&second) && !self.corner_by_corner { return false; }
 
-        if first.direction == second.direction
+        if first.direction.is_parallel_to(&second.direction)
         {
             if first.head_touches_head(&second) && !self.head_by_head { return false; }
             if first.side_touches_side(&second) && !self.side_by_side { return false; }
@@ -82,6 +83,114 @@ impl WordCompatibilitySettings
     }
 }
 
+/// Decides if two [words](Word) can be in the same [crossword](super::crossword::Crossword), used by [can_word_be_added](super::crossword::Crossword::can_word_be_added)
+///
+/// [WordCompatibilitySettings] is the default implementation, implement this trait for house rules that it can't express.
+///
+/// ## Example
+///
+/// ```
+/// # use crossword_generator::word::{Word, WordDirection, WordPosition, WordCompatibility, WordCompatibilitySettings};
+/// /// Default rules, but words can't cross on their first letters
+/// struct NoFirstLetterCrossings(WordCompatibilitySettings);
+///
+/// impl WordCompatibility for NoFirstLetterCrossings
+/// {
+///     fn are_words_compatible(&self, first: &Word, second: &Word) -> bool
+///     {
+///         if first.get_intersection_indices(second).is_some_and(|(i, j)| i == 0 || j == 0) { return false; }
+///         self.0.are_words_compatible(first, second)
+///     }
+/// }
+///
+/// let hello = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "hello", id: 0};
+/// let halo = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Down, value: "halo", id: 0};
+///
+/// assert!(WordCompatibilitySettings::default().are_words_compatible(&hello, &halo));
+/// assert!(!NoFirstLetterCrossings(WordCompatibilitySettings::default()).are_words_compatible(&hello, &halo));
+/// ```
+pub trait WordCompatibility: Send + Sync
+{
+    /// Checks if two [words](Word) are compatible
+    fn are_words_compatible(&self, first: &Word, second: &Word) -> bool;
+}
+
+impl WordCompatibility for WordCompatibilitySettings
+{
+    fn are_words_compatible(&self, first: &Word, second: &Word) -> bool
+    {
+        WordCompatibilitySettings::are_words_compatible(self, first, second)
+    }
+}
+
+/// A shared value that is compared by the address of its allocation instead of by its content
+///
+/// It lets trait objects such as [custom word compatibilities](CustomWordCompatibility) and [custom constraints](crate::constraint::CustomConstraint)
+/// be part of settings that derive [Eq] and [Ord]: two wrappers are equal only if they share the same allocation, and they are ordered by its address.
+pub struct ArcByAddress<T: ?Sized>(pub Arc<T>);
+
+impl<T: ?Sized> ArcByAddress<T>
+{
+    fn address(&self) -> usize
+    {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+impl<T: ?Sized> Clone for ArcByAddress<T>
+{
+    fn clone(&self) -> Self
+    {
+        ArcByAddress(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> std::fmt::Debug for ArcByAddress<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "ArcByAddress({:#x})", self.address())
+    }
+}
+
+impl<T: ?Sized> PartialEq for ArcByAddress<T>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.address() == other.address()
+    }
+}
+
+impl<T: ?Sized> Eq for ArcByAddress<T> {}
+
+impl<T: ?Sized> PartialOrd for ArcByAddress<T>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering>
+    {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for ArcByAddress<T>
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering
+    {
+        self.address().cmp(&other.address())
+    }
+}
+
+/// A shared user-defined [word compatibility](WordCompatibility), see [CrosswordGeneratorSettings::custom_word_compatibility](super::generator::CrosswordGeneratorSettings::custom_word_compatibility)
+pub type CustomWordCompatibility = ArcByAddress<dyn WordCompatibility>;
+
+impl CustomWordCompatibility
+{
+    /// Wraps a user-defined [word compatibility](WordCompatibility)
+    pub fn new(compatibility: impl WordCompatibility + 'static) -> CustomWordCompatibility
+    {
+        ArcByAddress(Arc::new(compatibility))
+    }
+}
+
 impl Default for WordCompatibilitySettings 
 {
     fn default() -> Self 
@@ -127,6 +236,12 @@ impl WordBoundingBox
         (self.x == other.x + other.w as isize && self.y + self.h as isize == other.y)
     }
 
+    fn distance(&self, other: &WordBoundingBox) -> usize
+    {
+        let gap = |start: isize, length: usize, other_start: isize, other_length: usize| (other_start - (start + length as isize - 1)).max(start - (other_start + other_length as isize - 1)).max(0) as usize;
+        gap(self.x, self.w, other.x, other.w).max(gap(self.y, self.h, other.y, other.h))
+    }
+
 }
 
 
@@ -139,104 +254,389 @@ pub struct WordPosition
 }
 
 /// Represents the direction of a [word](Word) in [crossword](super::crossword::Crossword)
+///
+/// [Left](WordDirection::Left) and [Up](WordDirection::Up) are used for word searches and right-to-left scripts, diagonal directions for puzzles for children and word searches.
+/// The [generator](super::generator::CrosswordGenerator) places them only if they are in its [directions](super::generator::CrosswordGeneratorSettings::directions).
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 pub enum WordDirection
 {
     #[default]
     Right,
-    Down
+    Down,
+    Left,
+    Up,
+    DownRight,
+    UpRight,
+    DownLeft,
+    UpLeft
 }
 
+/// Directions of the classic crossword, used when no other directions are given
+pub const DEFAULT_DIRECTIONS: [WordDirection; 2] = [WordDirection::Right, WordDirection::Down];
+
+/// All eight directions: horizontal, vertical and diagonal, both forwards and backwards
+pub const ALL_DIRECTIONS: [WordDirection; 8] =
+[
+    WordDirection::Right, WordDirection::Down, WordDirection::Left, WordDirection::Up,
+    WordDirection::DownRight, WordDirection::UpRight, WordDirection::DownLeft, WordDirection::UpLeft
+];
+
 impl WordDirection 
 {
+    /// Returns the direction the crossword gets when it is transposed (flipped over its main diagonal)
     pub fn opposite(&self) -> WordDirection
     {
         match *self
         {
             WordDirection::Down => WordDirection::Right,
-            WordDirection::Right => WordDirection::Down
+            WordDirection::Right => WordDirection::Down,
+            WordDirection::Left => WordDirection::Up,
+            WordDirection::Up => WordDirection::Left,
+            WordDirection::DownRight => WordDirection::DownRight,
+            WordDirection::UpRight => WordDirection::DownLeft,
+            WordDirection::DownLeft => WordDirection::UpRight,
+            WordDirection::UpLeft => WordDirection::UpLeft
         }
     } 
+
+    /// Returns the direction a word gets when it is flipped on its first cell: horizontal and vertical directions are swapped,
+    /// diagonal ones are mirrored vertically, so every direction changes and flipping twice gives the same direction
+    pub fn flipped(&self) -> WordDirection
+    {
+        match *self
+        {
+            WordDirection::Right => WordDirection::Down,
+            WordDirection::Down => WordDirection::Right,
+            WordDirection::Left => WordDirection::Up,
+            WordDirection::Up => WordDirection::Left,
+            WordDirection::DownRight => WordDirection::UpRight,
+            WordDirection::UpRight => WordDirection::DownRight,
+            WordDirection::DownLeft => WordDirection::UpLeft,
+            WordDirection::UpLeft => WordDirection::DownLeft
+        }
+    }
+
+    /// Returns the change of (x, y) from one character of a word to the next one
+    pub fn get_step(&self) -> (isize, isize)
+    {
+        match *self
+        {
+            WordDirection::Right => (1, 0),
+            WordDirection::Down => (0, 1),
+            WordDirection::Left => (-1, 0),
+            WordDirection::Up => (0, -1),
+            WordDirection::DownRight => (1, 1),
+            WordDirection::UpRight => (1, -1),
+            WordDirection::DownLeft => (-1, 1),
+            WordDirection::UpLeft => (-1, -1)
+        }
+    }
+
+    /// Returns true for [Right](WordDirection::Right) and [Left](WordDirection::Left)
+    pub fn is_horizontal(&self) -> bool
+    {
+        self.get_step().1 == 0
+    }
+
+    /// Returns true for [Down](WordDirection::Down) and [Up](WordDirection::Up)
+    pub fn is_vertical(&self) -> bool
+    {
+        self.get_step().0 == 0
+    }
+
+    /// Returns true for the directions that are neither horizontal nor vertical
+    pub fn is_diagonal(&self) -> bool
+    {
+        !self.is_horizontal() && !self.is_vertical()
+    }
+
+    /// Returns true if words in the directions lie on parallel lines, for example [Right](WordDirection::Right) and [Left](WordDirection::Left)
+    pub fn is_parallel_to(&self, other: &WordDirection) -> bool
+    {
+        let (dx, dy) = self.get_step();
+        let (other_dx, other_dy) = other.get_step();
+        dx * other_dy == dy * other_dx
+    }
 }
 
 
 /// Represents a word in [crossword](super::crossword::Crossword)
+///
+/// ## Fields
+///
+/// position -> [WordPosition] of the first letter
+///
+/// direction -> [WordDirection] the letters follow
+///
+/// value -> the string of the word
+///
+/// id -> tells apart entries with the same value, so the same word can be in a crossword more than once.
+/// An entry is identified by its value and id, the [generator](super::generator::CrosswordGenerator) numbers the copies of a word from 0.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
 pub struct Word<'a>
 {
     pub position: WordPosition,
     pub direction: WordDirection,
-    pub value: &'a str
+    pub value: &'a str,
+    #[serde(default, skip_serializing_if = "is_first_id")]
+    pub id: usize
+}
+
+fn is_first_id(id: &usize) -> bool
+{
+    *id == 0
 }
 
 impl<'a> Word<'a>
 {
     fn get_bounding_box(&self) -> WordBoundingBox
     {
-        match self.direction 
-        {
-            WordDirection::Right => WordBoundingBox { x: self.position.x, y: self.position.y, w: self.value.len(), h: 1 },
-            WordDirection::Down => WordBoundingBox { x: self.position.x, y: self.position.y, w: 1, h: self.value.len() },
-        }
+        let (first, last) = self.get_corners();
+        WordBoundingBox { x: first.x, y: first.y, w: (last.x - first.x + 1) as usize, h: (last.y - first.y + 1) as usize }
     }
 
     fn get_parallel_coordinate(&self) -> isize
     {
-        match self.direction
-        {
-            WordDirection::Right => self.position.y,
-            WordDirection::Down => self.position.x,
-        }
+        if self.direction.is_horizontal() { self.position.y } else { self.position.x }
     }
 
     #[allow(dead_code)]
     fn get_perpendicular_coordinate(&self) -> isize
     {
-        match self.direction
+        if self.direction.is_horizontal() { self.position.x } else { self.position.y }
+    }
+
+    /// Returns the position of the character with the index, it can be outside of the word
+    fn get_cell_position(&self, index: usize) -> WordPosition
+    {
+        let (dx, dy) = self.direction.get_step();
+        WordPosition { x: self.position.x + dx * index as isize, y: self.position.y + dy * index as isize }
+    }
+
+    /// Returns the index of the character in the cell, the cell has to be on the line of the word
+    fn get_index_of(&self, cell: &WordPosition) -> usize
+    {
+        let (dx, dy) = self.direction.get_step();
+        ((cell.x - self.position.x) * dx + (cell.y - self.position.y) * dy) as usize
+    }
+
+    /// Returns true if the cell is on the line of the [word](Word), in the word or outside of it
+    fn is_on_line(&self, cell: &WordPosition) -> bool
+    {
+        let (dx, dy) = self.direction.get_step();
+        (cell.x - self.position.x) * dy == (cell.y - self.position.y) * dx
+    }
+
+    /// Returns pairs of indices of the characters of the [words](Word) that are in the same cell
+    fn get_shared_cells(&self, other: &Word) -> Vec<(usize, usize)>
+    {
+        let other_cells = other.get_cells();
+        self.get_cells().iter().enumerate()
+            .flat_map(|(i, (cell, _))| other_cells.iter().enumerate().filter(move |(_, (other_cell, _))| other_cell == cell).map(move |(j, _)| (i, j)))
+            .collect()
+    }
+
+    /// Returns pairs of indices of the characters of the [words](Word) in neighbouring cells, side by side ones or only corner by corner ones
+    ///
+    /// Characters that are both next to (or in) a cell where the words cross are left out, because words that cross diagonally always touch there.
+    fn get_touching_cells(&self, other: &Word, by_corners: bool) -> Vec<(usize, usize)>
+    {
+        let shared = self.get_shared_cells(other);
+        let other_cells = other.get_cells();
+        self.get_cells().iter().enumerate()
+            .flat_map(|(i, (cell, _))| other_cells.iter().enumerate().map(move |(j, (other_cell, _))| (i, j, (cell.x - other_cell.x).abs(), (cell.y - other_cell.y).abs())))
+            .filter(|(_, _, dx, dy)| if by_corners { *dx == 1 && *dy == 1 } else { dx + dy == 1 })
+            .filter(|(i, j, _, _)| !shared.iter().any(|(si, sj)| si.abs_diff(*i) <= 1 && sj.abs_diff(*j) <= 1))
+            .map(|(i, j, _, _)| (i, j))
+            .collect()
+    }
+
+    /// Returns true if the bounding box geometry can be used, it is exact only for words that are not diagonal
+    fn is_axis_aligned_with(&self, other: &Word) -> bool
+    {
+        !self.direction.is_diagonal() && !other.direction.is_diagonal()
+    }
+
+    /// Returns the top left and the bottom right cell of the smallest rectangle that contains the [word](Word)
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// let word = Word{position: WordPosition { x: 3, y: 2 }, direction: WordDirection::Left, value: "olleh", id: 0};
+    ///
+    /// assert_eq!(word.get_corners(), (WordPosition { x: -1, y: 2 }, WordPosition { x: 3, y: 2 }));
+    /// ```
+    pub fn get_corners(&self) -> (WordPosition, WordPosition)
+    {
+        let last = self.get_cell_position(self.value.chars().count().max(1) - 1);
+        (
+            WordPosition { x: self.position.x.min(last.x), y: self.position.y.min(last.y) },
+            WordPosition { x: self.position.x.max(last.x), y: self.position.y.max(last.y) }
+        )
+    }
+
+    /// Returns the cells of the [word](Word) with their characters, from the first character to the last
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// let word = Word{position: WordPosition { x: 1, y: 2 }, direction: WordDirection::Down, value: "ok", id: 0};
+    ///
+    /// assert_eq!(word.get_cells(), vec![(WordPosition { x: 1, y: 2 }, 'o'), (WordPosition { x: 1, y: 3 }, 'k')]);
+    /// ```
+    pub fn get_cells(&self) -> Vec<(WordPosition, char)>
+    {
+        self.value.chars().enumerate()
+            .map(|(index, ch)| (self.get_cell_position(index), ch))
+            .collect()
+    }
+
+    /// Returns the key the clue of the entry is stored under, the value for the first copy of a word and value#id for the next ones
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordDirection, WordPosition};
+    /// let first = Word{position: WordPosition { x: 0, y: 0 }, direction: WordDirection::Right, value: "cat", id: 0};
+    /// let second = Word{position: WordPosition { x: 0, y: 2 }, direction: WordDirection::Right, value: "cat", id: 1};
+    ///
+    /// assert_eq!(first.get_clue_key(), "cat");
+    /// assert_eq!(second.get_clue_key(), "cat#1");
+    /// ```
+    pub fn get_clue_key(&self) -> String
+    {
+        match self.id
         {
-            WordDirection::Right => self.position.x,
-            WordDirection::Down => self.position.y,
+            0 => self.value.to_owned(),
+            id => format!("{}#{}", self.value, id)
         }
     }
 
-    /// Returns true if two [words](Word) are intersecting 
+    /// Returns true if two [words](Word) are intersecting, they have a common cell
     pub fn intersects(&self, other: &Word) -> bool 
     {
-        self.get_bounding_box().intersects(&other.get_bounding_box())
+        if self.is_axis_aligned_with(other)
+        {
+            return self.get_bounding_box().intersects(&other.get_bounding_box());
+        }
+        self.intersects_by_cells(other)
+    }
+
+    fn intersects_by_cells(&self, other: &Word) -> bool
+    {
+        !self.get_shared_cells(other).is_empty()
     }
 
     fn sides_touch(&self, other: &Word) -> bool
     {
-        self.get_bounding_box().sides_touch(&other.get_bounding_box())
+        if self.is_axis_aligned_with(other)
+        {
+            return self.get_bounding_box().sides_touch(&other.get_bounding_box());
+        }
+        self.sides_touch_by_cells(other)
+    }
+
+    fn sides_touch_by_cells(&self, other: &Word) -> bool
+    {
+        !self.get_touching_cells(other, false).is_empty()
+    }
+
+    /// Returns the distance between the nearest cells of two [words](Word), counted in cells in the direction where they are further apart
+    ///
+    /// Crossing words have distance 0, and words that touch (even with corners) have distance 1.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::{Word, WordPosition, WordDirection};
+    /// let cat = Word{ position: WordPosition{x: 0, y: 0}, direction: WordDirection::Right, value: "cat", id: 0};
+    ///
+    /// assert_eq!(cat.get_distance(&Word{ position: WordPosition{x: 3, y: 1}, direction: WordDirection::Down, value: "ok", id: 0}), 1);
+    /// assert_eq!(cat.get_distance(&Word{ position: WordPosition{x: 1, y: 3}, direction: WordDirection::Right, value: "ok", id: 0}), 3);
+    /// ```
+    pub fn get_distance(&self, other: &Word) -> usize
+    {
+        if self.is_axis_aligned_with(other)
+        {
+            return self.get_bounding_box().distance(&other.get_bounding_box());
+        }
+        self.get_distance_by_cells(other)
+    }
+
+    fn get_distance_by_cells(&self, other: &Word) -> usize
+    {
+        let other_cells = other.get_cells();
+        self.get_cells().iter()
+            .flat_map(|(cell, _)| other_cells.iter().map(move |(other_cell, _)| cell.x.abs_diff(other_cell.x).max(cell.y.abs_diff(other_cell.y))))
+            .min()
+            .unwrap_or(0)
     }
 
     /// Returns true if two [words](Word) are corner by corner (check [WordCompatibilitySettings::corner_by_corner])
+    ///
+    /// Words touch only by corners if they have diagonally neighbouring characters, but no common cell and no characters side by side.
     pub fn corners_touch(&self, other: &Word) -> bool
     {
-        self.get_bounding_box().corners_touch(&other.get_bounding_box())
+        if self.is_axis_aligned_with(other)
+        {
+            return self.get_bounding_box().corners_touch(&other.get_bounding_box());
+        }
+        self.corners_touch_by_cells(other)
+    }
+
+    fn corners_touch_by_cells(&self, other: &Word) -> bool
+    {
+        !self.get_touching_cells(other, true).is_empty() && !self.intersects_by_cells(other) && !self.sides_touch_by_cells(other) && !self.head_touches_head_by_cells(other)
     }
 
     /// Returns true if two [words](Word) are side by side (check [WordCompatibilitySettings::side_by_side])
     pub fn side_touches_side(&self, other: &Word) -> bool
     {
-        self.direction == other.direction &&
-        self.sides_touch(other) && 
-        self.get_parallel_coordinate() != other.get_parallel_coordinate()
+        if self.is_axis_aligned_with(other)
+        {
+            return self.direction.is_parallel_to(&other.direction) &&
+                self.sides_touch(other) && 
+                self.get_parallel_coordinate() != other.get_parallel_coordinate();
+        }
+        self.side_touches_side_by_cells(other)
+    }
+
+    fn side_touches_side_by_cells(&self, other: &Word) -> bool
+    {
+        self.direction.is_parallel_to(&other.direction) &&
+        self.sides_touch_by_cells(other) &&
+        !self.is_on_line(&other.position)
     }
 
     /// Returns true if two [words](Word) are side by head (check [WordCompatibilitySettings::side_by_head])
     pub fn side_touches_head(&self, other: &Word) -> bool
     {
-        self.direction != other.direction &&
+        !self.direction.is_parallel_to(&other.direction) &&
         self.sides_touch(other)
     }
 
     /// Returns true if two [words](Word) are head by head (check [WordCompatibilitySettings::head_by_head])
     pub fn head_touches_head(&self, other: &Word) -> bool
     {
-        self.direction == other.direction &&
-        self.sides_touch(other) && 
-        self.get_parallel_coordinate() == other.get_parallel_coordinate()
+        if self.is_axis_aligned_with(other)
+        {
+            return self.direction.is_parallel_to(&other.direction) &&
+                self.sides_touch(other) && 
+                self.get_parallel_coordinate() == other.get_parallel_coordinate();
+        }
+        self.head_touches_head_by_cells(other)
+    }
+
+    fn head_touches_head_by_cells(&self, other: &Word) -> bool
+    {
+        let (dx, dy) = self.direction.get_step();
+        let other_cells = other.get_cells();
+        self.direction.is_parallel_to(&other.direction) &&
+        self.is_on_line(&other.position) &&
+        !self.intersects_by_cells(other) &&
+        self.get_cells().iter().any(|(cell, _)| other_cells.iter().any(|(other_cell, _)| (other_cell.x - cell.x, other_cell.y - cell.y) == (dx, dy) || (cell.x - other_cell.x, cell.y - other_cell.y) == (dx, dy)))
     }
 
     /// Returns the indices of the characters in the intersection of the [words](Word) if they are intersecting
@@ -246,8 +646,8 @@ impl<'a> Word<'a>
     /// ## Examples
     /// ```
     /// # use crossword_generator::word::{Word, WordPosition, WordDirection};
-    /// let w1 = Word{ position: WordPosition{x: 0, y: 1}, direction: WordDirection::Right, value: "hello"};
-    /// let w2 = Word{ position: WordPosition{x: 4, y: 0}, direction: WordDirection::Down, value: "world"};
+    /// let w1 = Word{ position: WordPosition{x: 0, y: 1}, direction: WordDirection::Right, value: "hello", id: 0};
+    /// let w2 = Word{ position: WordPosition{x: 4, y: 0}, direction: WordDirection::Down, value: "world", id: 0};
     /// 
     /// //         w
     /// // h e l l o
@@ -263,13 +663,29 @@ impl<'a> Word<'a>
     pub fn get_intersection_indices(&self, other: &Word) -> Option<(usize, usize)>
     {
         if !self.intersects(other) { return None; }
-        if self.direction == other.direction { return None; }
+        if self.direction.is_parallel_to(&other.direction) { return None; }
+
+        if !self.is_axis_aligned_with(other)
+        {
+            return self.get_intersection_indices_by_cells(other);
+        }
 
-        match self.direction
+        let cell = if self.direction.is_horizontal()
         {
-            WordDirection::Right => Some(((other.position.x - self.position.x) as usize, (self.position.y - other.position.y) as usize)),
-            WordDirection::Down => Some(((other.position.y - self.position.y) as usize, (self.position.x - other.position.x) as usize))
+            WordPosition { x: other.position.x, y: self.position.y }
         }
+        else
+        {
+            WordPosition { x: self.position.x, y: other.position.y }
+        };
+
+        Some((self.get_index_of(&cell), other.get_index_of(&cell)))
+    }
+
+    fn get_intersection_indices_by_cells(&self, other: &Word) -> Option<(usize, usize)>
+    {
+        if self.direction.is_parallel_to(&other.direction) { return None; }
+        self.get_shared_cells(other).first().copied()
     }
 
     /// Returns all possible ways to add another [word](Word) on top of this 
@@ -278,7 +694,7 @@ impl<'a> Word<'a>
     /// ```
     /// # use crossword_generator::word::{Word, WordPosition, WordDirection};
     /// # use std::collections::BTreeSet;
-    /// let w1 = Word{ position: WordPosition{x: 0, y: 3}, direction: WordDirection::Right, value: "hello"};
+    /// let w1 = Word{ position: WordPosition{x: 0, y: 3}, direction: WordDirection::Right, value: "hello", id: 0};
     /// 
     /// 
     /// //     w w 
@@ -290,33 +706,56 @@ impl<'a> Word<'a>
     /// //         d
     /// 
     /// assert_eq!(w1.calculate_possible_ways_to_add_word("world"), BTreeSet::from([
-    ///     Word{ position: WordPosition{x: 2, y: 0}, direction: WordDirection::Down, value: "world"},
-    ///     Word{ position: WordPosition{x: 3, y: 0}, direction: WordDirection::Down, value: "world"},
-    ///     Word{ position: WordPosition{x: 4, y: 2}, direction: WordDirection::Down, value: "world"}
+    ///     Word{ position: WordPosition{x: 2, y: 0}, direction: WordDirection::Down, value: "world", id: 0},
+    ///     Word{ position: WordPosition{x: 3, y: 0}, direction: WordDirection::Down, value: "world", id: 0},
+    ///     Word{ position: WordPosition{x: 4, y: 2}, direction: WordDirection::Down, value: "world", id: 0}
     /// ]));
     ///
     /// ```
     pub fn calculate_possible_ways_to_add_word(&self, word: &'a str) -> BTreeSet<Word<'a>>
+    {
+        self.calculate_possible_ways_to_add_word_in_directions(word, &DEFAULT_DIRECTIONS)
+    }
+
+    /// Returns all possible ways to add another [word](Word) on top of this in one of the directions that are not [parallel](WordDirection::is_parallel_to) to this word
+    /// 
+    /// ## Examples
+    /// ```
+    /// # use crossword_generator::word::{Word, WordPosition, WordDirection};
+    /// # use std::collections::BTreeSet;
+    /// let w1 = Word{ position: WordPosition{x: 0, y: 1}, direction: WordDirection::Right, value: "hello", id: 0};
+    /// 
+    /// //         o
+    /// // h e l l o
+    /// //         t
+    /// 
+    /// assert_eq!(w1.calculate_possible_ways_to_add_word_in_directions("to", &[WordDirection::Up, WordDirection::Left]), BTreeSet::from([
+    ///     Word{ position: WordPosition{x: 4, y: 2}, direction: WordDirection::Up, value: "to", id: 0},
+    /// ]));
+    /// ```
+    pub fn calculate_possible_ways_to_add_word_in_directions(&self, word: &'a str, directions: &[WordDirection]) -> BTreeSet<Word<'a>>
     {
         let mut pos_ways: BTreeSet<Word<'a>> = BTreeSet::new();
         let common_chars = word.chars().filter(|c| self.value.contains(*c)).collect::<Vec<char>>();
 
-        for char in common_chars
+        for direction in directions.iter().filter(|d| !d.is_parallel_to(&self.direction))
         {
-            for (word_ind, self_ind) in word.chars().enumerate().filter_map(|c| if c.1 == char { Some(c.0) } else { None } ).cartesian_product(self.value.chars().enumerate().filter_map(|c| if c.1 == char { Some(c.0) } else { None } ))
+            let (dx, dy) = direction.get_step();
+            for char in common_chars.iter()
             {
-                pos_ways.insert(
-                    Word
-                    {
-                        position: match self.direction
+                for (word_ind, self_ind) in word.chars().enumerate().filter_map(|c| if c.1 == *char { Some(c.0) } else { None } ).cartesian_product(self.value.chars().enumerate().filter_map(|c| if c.1 == *char { Some(c.0) } else { None } ))
+                {
+                    let cell = self.get_cell_position(self_ind);
+                    pos_ways.insert(
+                        Word
                         {
-                            WordDirection::Right => WordPosition{ x: self.position.x + self_ind as isize, y: self.position.y - word_ind as isize},
-                            WordDirection::Down  => WordPosition{ x: self.position.x - word_ind as isize, y: self.position.y + self_ind as isize},
-                        },
-                        direction: self.direction.opposite(),
-                        value: word
-                    }
-                );
+                            position: WordPosition{ x: cell.x - dx * word_ind as isize, y: cell.y - dy * word_ind as isize },
+                            direction: direction.clone(),
+                            value: word,
+                            id: 0
+                        }
+                    );
+                }
             }
         }
 
@@ -336,8 +775,8 @@ mod tests
     #[test]
     fn test_word_bounding_box_intersects()
     {
-        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
         
         let mut comp = vec![];
         for y in -2isize..=2
@@ -409,8 +848,8 @@ mod tests
     #[test]
     fn test_word_bounding_box_side_touches_side()
     {
-        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
         
         let mut comp = vec![];
         for y in -2isize..=2
@@ -482,8 +921,8 @@ mod tests
     #[test]
     fn test_word_bounding_box_side_touches_head()
     {
-        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
         
         let mut comp = vec![];
         for y in -2isize..=2
@@ -555,8 +994,8 @@ mod tests
     #[test]
     fn test_word_bounding_box_head_touches_head()
     {
-        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
         
         let mut comp = vec![];
         for y in -2isize..=2
@@ -628,8 +1067,8 @@ mod tests
     #[test]
     fn test_word_bounding_box_corners()
     {
-        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
         
         let mut comp = vec![];
         for y in -2isize..=2
@@ -701,8 +1140,8 @@ mod tests
     #[test]
     fn test_word_bounding_box_get_intersection_indices()
     {
-        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+        let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+        let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
 
         assert_eq!(first.get_intersection_indices(&second), None);
 
@@ -725,8 +1164,8 @@ mod tests
         {
             let settings = WordCompatibilitySettings { side_by_side: a != 0, head_by_head: b != 0, side_by_head: c != 0, corner_by_corner: d != 0 };
 
-            let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan" };
-            let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax" };
+            let mut first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "hayastan", id: 0 };
+            let mut second = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "arcax", id: 0 };
             
             let mut comp = vec![];
             for y in -2isize..=2
@@ -796,4 +1235,91 @@ mod tests
         }
 
     }
+    #[test]
+    fn test_word_left_up_geometry()
+    {
+        let hello = Word{ position: WordPosition{ x: 4, y: 0 }, direction: WordDirection::Left, value: "hello", id: 0 };
+        let yo = Word{ position: WordPosition{ x: 0, y: 1 }, direction: WordDirection::Up, value: "yo", id: 0 };
+
+        assert_eq!(hello.get_cells().iter().map(|(p, _)| p.x).collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+        assert_eq!(yo.get_corners(), (WordPosition{ x: 0, y: 0 }, WordPosition{ x: 0, y: 1 }));
+        assert_eq!(hello.get_intersection_indices(&yo), Some((4, 1)));
+        assert_eq!(yo.get_intersection_indices(&hello), Some((1, 4)));
+        assert!(WordCompatibilitySettings::default().are_words_compatible(&hello, &yo));
+
+        assert_eq!(hello.calculate_possible_ways_to_add_word_in_directions("yo", &[WordDirection::Up, WordDirection::Left, WordDirection::Right]), BTreeSet::from([yo.clone()]));
+        assert_eq!(yo.calculate_possible_ways_to_add_word_in_directions("hello", &[WordDirection::Left, WordDirection::Up]), BTreeSet::from([hello.clone()]));
+
+        let right = Word{ position: WordPosition{ x: 5, y: 0 }, direction: WordDirection::Right, value: "world", id: 0 };
+        assert!(hello.head_touches_head(&right));
+        assert!(!WordCompatibilitySettings::default().are_words_compatible(&hello, &right));
+        assert!(!WordCompatibilitySettings::default().are_words_compatible(&hello, &Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "oh", id: 0 }));
+    }
+
+    #[test]
+    fn test_word_diagonal_geometry()
+    {
+        let cat = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::DownRight, value: "cat", id: 0 };
+        let bat = Word{ position: WordPosition{ x: 0, y: 1 }, direction: WordDirection::Right, value: "bat", id: 0 };
+
+        assert!(cat.intersects(&bat));
+        assert!(!cat.sides_touch(&bat));
+        assert_eq!(cat.get_intersection_indices(&bat), Some((1, 1)));
+        assert_eq!(cat.get_distance(&bat), 0);
+        assert!(WordCompatibilitySettings::default().are_words_compatible(&cat, &bat));
+        assert!(cat.calculate_possible_ways_to_add_word_in_directions("bat", &[WordDirection::Right]).contains(&bat));
+
+        // x-shaped crossing without a shared cell
+        let ab = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::DownRight, value: "ab", id: 0 };
+        let cd = Word{ position: WordPosition{ x: 0, y: 1 }, direction: WordDirection::UpRight, value: "cd", id: 0 };
+        assert!(!ab.intersects(&cd));
+        assert!(ab.sides_touch(&cd));
+        assert_eq!(ab.get_intersection_indices(&cd), None);
+        assert!(!WordCompatibilitySettings::default().are_words_compatible(&ab, &cd));
+
+        let ef = Word{ position: WordPosition{ x: 2, y: 2 }, direction: WordDirection::DownRight, value: "ef", id: 0 };
+        assert!(ab.head_touches_head(&ef));
+        assert!(!ab.side_touches_side(&ef));
+        assert!(!ab.corners_touch(&ef));
+
+        let gh = Word{ position: WordPosition{ x: 1, y: 0 }, direction: WordDirection::DownRight, value: "gh", id: 0 };
+        assert!(ab.side_touches_side(&gh));
+        assert!(!ab.head_touches_head(&gh));
+    }
+
+    #[test]
+    fn test_word_cell_geometry_matches_bounding_boxes()
+    {
+        let directions = [WordDirection::Right, WordDirection::Down, WordDirection::Left, WordDirection::Up];
+        let first = Word{ position: WordPosition{ x: 0, y: 0 }, direction: WordDirection::Right, value: "abc", id: 0 };
+        for direction in directions
+        {
+            for x in -4..=4
+            {
+                for y in -3..=3
+                {
+                    let second = Word{ position: WordPosition{ x, y }, direction: direction.clone(), value: "de", id: 0 };
+                    assert_eq!(first.intersects(&second), first.intersects_by_cells(&second), "{second:?}");
+                    assert_eq!(first.sides_touch(&second), first.sides_touch_by_cells(&second), "{second:?}");
+                    assert_eq!(first.corners_touch(&second), first.corners_touch_by_cells(&second), "{second:?}");
+                    assert_eq!(first.side_touches_side(&second), first.side_touches_side_by_cells(&second), "{second:?}");
+                    assert_eq!(first.head_touches_head(&second), first.head_touches_head_by_cells(&second), "{second:?}");
+                    assert_eq!(first.get_distance(&second), first.get_distance_by_cells(&second), "{second:?}");
+                    assert_eq!(first.get_intersection_indices(&second), first.get_intersection_indices_by_cells(&second), "{second:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arc_by_address()
+    {
+        let first = CustomWordCompatibility::new(WordCompatibilitySettings::default());
+        let second = CustomWordCompatibility::new(WordCompatibilitySettings::default());
+
+        assert_eq!(first, first.clone());
+        assert_ne!(first, second);
+        assert_eq!(first.cmp(&second), first.address().cmp(&second.address()));
+        assert!(format!("{:?}", first).starts_with("ArcByAddress(0x"));
+    }
 }
@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+
+use rand::{Rng, SeedableRng};
+use rand::distributions::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Serialize, Deserialize};
+
+use super::word::*;
+use super::crossword::*;
+
+
+/// Represents the letters used to fill the cells of a [word search](WordSearch) that are not covered by any word
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum LetterSource
+{
+    /// Every letter of the string is chosen with the same probability (a letter given twice is chosen twice as often)
+    Alphabet(String),
+    /// Letters are chosen with probabilities proportional to their weights, for example letter frequencies of a language
+    Frequencies(BTreeMap<char, u32>)
+}
+
+impl Default for LetterSource
+{
+    fn default() -> Self
+    {
+        LetterSource::Alphabet("abcdefghijklmnopqrstuvwxyz".to_owned())
+    }
+}
+
+/// Represents settings for [generating word searches](WordSearch::generate)
+///
+/// ## Fields
+///
+/// width, height -> size of the grid
+///
+/// directions -> [directions](WordDirection) the words can be placed in, all eight by default
+///
+/// allow_overlaps -> if true, words can cross each other on cells with the same letter, parallel words never overlap
+///
+/// letters -> [LetterSource] for the cells that are not covered by any word
+///
+/// seed -> seed of the random generator, the same words and settings always give the same word search
+///
+/// max_attempts -> how many times the placement is restarted before giving up with [WordSearchError::NotFound]
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WordSearchSettings
+{
+    pub width: usize,
+    pub height: usize,
+    pub directions: Vec<WordDirection>,
+    pub allow_overlaps: bool,
+    pub letters: LetterSource,
+    pub seed: u64,
+    pub max_attempts: usize
+}
+
+impl Default for WordSearchSettings
+{
+    fn default() -> Self
+    {
+        WordSearchSettings
+        {
+            width: 12,
+            height: 12,
+            directions: ALL_DIRECTIONS.to_vec(),
+            allow_overlaps: true,
+            letters: LetterSource::default(),
+            seed: 0,
+            max_attempts: 100
+        }
+    }
+}
+
+/// Represents an error of [generating word searches](WordSearch::generate)
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum WordSearchError
+{
+    /// The width or height is zero or no [direction](WordSearchSettings::directions) is given
+    EmptyGrid,
+    /// The word is empty or doesn't fit in the grid in any of the directions
+    WordDoesNotFit(String),
+    /// The [letter source](WordSearchSettings::letters) has no letters or all their weights are zero
+    NoLetters,
+    /// The words couldn't be placed together in [max_attempts](WordSearchSettings::max_attempts) attempts
+    NotFound
+}
+
+impl std::fmt::Display for WordSearchError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            WordSearchError::EmptyGrid => write!(f, "the grid has no cells or no direction is allowed"),
+            WordSearchError::WordDoesNotFit(word) => write!(f, "the word \"{}\" doesn't fit in the grid", word),
+            WordSearchError::NoLetters => write!(f, "there are no letters to fill the empty cells with"),
+            WordSearchError::NotFound => write!(f, "the words couldn't be placed together, try a bigger grid or more attempts"),
+        }
+    }
+}
+
+impl std::error::Error for WordSearchError {}
+
+/// Represents a word search puzzle: a grid of letters with hidden [words](Word)
+///
+/// The answer key is an [absolute crossword](Crossword::new_absolute) with the words on the coordinates of the grid,
+/// the other cells of the grid are filled with random letters.
+///
+/// ## Example
+///
+/// ```text
+/// x c a t q      cat -> Right from (1, 0)
+/// d o g m w      dog -> Right from (0, 1)
+/// b w e t z      cow -> Down from (1, 0)
+/// ```
+#[derive(Clone, Eq, PartialEq, PartialOrd, Ord, Default, Debug, Serialize, Deserialize)]
+pub struct WordSearch<'a>
+{
+    #[serde(borrow)]
+    answer_key: Crossword<'a>,
+    grid: Vec<Vec<char>>
+}
+
+/// Returns all placements of the word that fit in the grid in the given directions
+fn get_placements<'a>(word: &'a str, width: usize, height: usize, directions: &[WordDirection]) -> Vec<Word<'a>>
+{
+    let length = word.chars().count() as isize;
+    let mut placements = vec![];
+    if length == 0 { return placements; }
+
+    for direction in directions.iter()
+    {
+        let (dx, dy) = direction.get_step();
+        for y in 0..height as isize
+        {
+            for x in 0..width as isize
+            {
+                let (last_x, last_y) = (x + dx * (length - 1), y + dy * (length - 1));
+                if (0..width as isize).contains(&last_x) && (0..height as isize).contains(&last_y)
+                {
+                    placements.push(Word{ position: WordPosition{ x, y }, direction: direction.clone(), value: word, id: 0 });
+                }
+            }
+        }
+    }
+
+    placements
+}
+
+impl<'a> WordSearch<'a>
+{
+    /// Generates a random word search with all of the words
+    ///
+    /// The words are placed from the longest one on random free positions in random [directions](WordSearchSettings::directions),
+    /// if a word can't be placed, the placement starts again. Words cross each other only if [allowed](WordSearchSettings::allow_overlaps)
+    /// and only on cells with the same letter. The remaining cells are filled from the [letter source](WordSearchSettings::letters).
+    /// A word that is in the list several times is placed once, its copies couldn't be told apart in the grid.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word_search::{WordSearch, WordSearchSettings};
+    /// let words = ["hello", "world", "crossword", "puzzle"];
+    /// let settings = WordSearchSettings { width: 10, height: 10, seed: 3, ..Default::default() };
+    /// let word_search = WordSearch::generate(&words, &settings).unwrap();
+    ///
+    /// assert_eq!(word_search.get_size(), (10, 10));
+    /// assert_eq!(word_search.get_answer_key().get_words().len(), 4);
+    /// for word in word_search.get_answer_key().get_words()
+    /// {
+    ///     for (position, ch) in word.get_cells()
+    ///     {
+    ///         assert_eq!(word_search.get_grid()[position.y as usize][position.x as usize], ch);
+    ///     }
+    /// }
+    /// assert_eq!(WordSearch::generate(&words, &settings), Ok(word_search));
+    /// ```
+    pub fn generate(words: &[&'a str], settings: &WordSearchSettings) -> Result<WordSearch<'a>, WordSearchError>
+    {
+        let (width, height) = (settings.width, settings.height);
+        if width == 0 || height == 0 || settings.directions.is_empty()
+        {
+            return Err(WordSearchError::EmptyGrid);
+        }
+
+        let (letters, weights): (Vec<char>, Vec<u32>) = match &settings.letters
+        {
+            LetterSource::Alphabet(alphabet) => alphabet.chars().map(|ch| (ch, 1)).unzip(),
+            LetterSource::Frequencies(frequencies) => frequencies.iter().map(|(ch, weight)| (*ch, *weight)).unzip()
+        };
+        let distribution = WeightedIndex::new(&weights).map_err(|_| WordSearchError::NoLetters)?;
+
+        let mut words = words.to_vec();
+        words.sort_by_key(|word| (std::cmp::Reverse(word.chars().count()), *word));
+        words.dedup();
+        let mut placements = Vec::with_capacity(words.len());
+        for word in words.iter()
+        {
+            let word_placements = get_placements(word, width, height, &settings.directions);
+            if word_placements.is_empty()
+            {
+                return Err(WordSearchError::WordDoesNotFit(word.to_string()));
+            }
+            placements.push(word_placements);
+        }
+
+        let mut rng = StdRng::seed_from_u64(settings.seed);
+        for _ in 0..settings.max_attempts.max(1)
+        {
+            let mut grid: Vec<Vec<Option<char>>> = vec![vec![None; width]; height];
+            let mut placed: Vec<Word<'a>> = vec![];
+
+            for word_placements in placements.iter_mut()
+            {
+                word_placements.shuffle(&mut rng);
+                let fitting = word_placements.iter().find(|word|
+                {
+                    word.get_cells().iter().all(|(position, ch)| match grid[position.y as usize][position.x as usize]
+                    {
+                        None => true,
+                        Some(other) => settings.allow_overlaps && other == *ch
+                    }) &&
+                    !placed.iter().any(|other| other.direction.is_parallel_to(&word.direction) && other.intersects(word))
+                });
+
+                match fitting
+                {
+                    Some(word) =>
+                    {
+                        word.get_cells().into_iter().for_each(|(position, ch)| grid[position.y as usize][position.x as usize] = Some(ch));
+                        placed.push(word.clone());
+                    },
+                    None => break
+                }
+            }
+
+            if placed.len() == placements.len()
+            {
+                let grid = grid.into_iter()
+                    .map(|row| row.into_iter().map(|cell| cell.unwrap_or_else(|| letters[rng.sample(&distribution)])).collect())
+                    .collect();
+                return Ok(WordSearch { answer_key: Crossword::new_absolute(&placed), grid });
+            }
+        }
+
+        Err(WordSearchError::NotFound)
+    }
+
+    /// Returns the answer key, an [absolute crossword](Crossword::new_absolute) with the hidden [words](Word) on the coordinates of the grid
+    pub fn get_answer_key(&self) -> &Crossword<'a>
+    {
+        &self.answer_key
+    }
+
+    /// Returns the letters of the puzzle, row by row
+    pub fn get_grid(&self) -> &Vec<Vec<char>>
+    {
+        &self.grid
+    }
+
+    /// Returns (width, height) of the grid
+    pub fn get_size(&self) -> (usize, usize)
+    {
+        (self.grid.first().map_or(0, |row| row.len()), self.grid.len())
+    }
+
+    /// Returns the hidden words in alphabetical order, the list printed next to the puzzle
+    pub fn get_word_list(&self) -> Vec<&'a str>
+    {
+        let mut words: Vec<&'a str> = self.answer_key.get_words().iter().map(|word| word.value).collect();
+        words.sort();
+        words
+    }
+
+    /// Returns a printable [String] with the letters of the puzzle separated by spaces, one row per line
+    pub fn generate_string(&self) -> String
+    {
+        self.grid.iter().map(|row| row.iter().map(|ch| ch.to_string()).collect::<Vec<_>>().join(" ") + "\n").collect()
+    }
+
+    /// Returns a printable [String] of the answer key, the letters of the hidden words are kept and the other cells are replaced with '.'
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use crossword_generator::word::WordDirection;
+    /// # use crossword_generator::word_search::{WordSearch, WordSearchSettings};
+    /// let settings = WordSearchSettings { width: 4, height: 2, directions: vec![WordDirection::Right], ..Default::default() };
+    /// let word_search = WordSearch::generate(&["word", "key"], &settings).unwrap();
+    ///
+    /// let answer_key = word_search.generate_answer_key_string();
+    /// assert!(answer_key == "w o r d\nk e y .\n" || answer_key == "w o r d\n. k e y\n" ||
+    ///         answer_key == "k e y .\nw o r d\n" || answer_key == ". k e y\nw o r d\n");
+    /// ```
+    pub fn generate_answer_key_string(&self) -> String
+    {
+        let (width, height) = self.get_size();
+        let mut table = vec![vec!['.'; width]; height];
+        for word in self.answer_key.get_words()
+        {
+            for (position, ch) in word.get_cells()
+            {
+                table[position.y as usize][position.x as usize] = ch;
+            }
+        }
+
+        table.iter().map(|row| row.iter().map(|ch| ch.to_string()).collect::<Vec<_>>().join(" ") + "\n").collect()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+
+    use super::*;
+
+    #[test]
+    fn test_generate_word_search() {
+        let words = ["apple", "banana", "cherry", "date", "fig", "grape", "lemon"];
+        for seed in 0..5
+        {
+            for allow_overlaps in [true, false]
+            {
+                let settings = WordSearchSettings { width: 9, height: 8, seed, allow_overlaps, letters: LetterSource::Alphabet("xyz".to_owned()), ..Default::default() };
+                let word_search = WordSearch::generate(&words, &settings).unwrap();
+
+                assert_eq!(word_search.get_size(), (9, 8));
+                assert_eq!(word_search.get_word_list(), words.to_vec());
+
+                let placed: Vec<_> = word_search.get_answer_key().get_words().iter().collect();
+                let mut covered = vec![vec![0; 9]; 8];
+                for word in placed.iter()
+                {
+                    for (position, ch) in word.get_cells()
+                    {
+                        assert_eq!(word_search.get_grid()[position.y as usize][position.x as usize], ch);
+                        covered[position.y as usize][position.x as usize] += 1;
+                    }
+                    for other in placed.iter().filter(|other| *other != word)
+                    {
+                        assert!(!(word.direction.is_parallel_to(&other.direction) && word.intersects(other)));
+                    }
+                }
+                for (y, row) in covered.iter().enumerate()
+                {
+                    for (x, count) in row.iter().enumerate()
+                    {
+                        if *count == 0 { assert!("xyz".contains(word_search.get_grid()[y][x])); }
+                        if !allow_overlaps { assert!(*count <= 1); }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_search_letter_frequencies() {
+        let settings = WordSearchSettings { width: 5, height: 5, letters: LetterSource::Frequencies(BTreeMap::from([('q', 1), ('z', 0)])), ..Default::default() };
+        let word_search = WordSearch::generate(&["abc"], &settings).unwrap();
+
+        assert_eq!(word_search.get_grid().iter().flatten().filter(|ch| **ch == 'q').count(), 22);
+        assert_eq!(word_search.generate_answer_key_string().chars().filter(|ch| *ch == '.').count(), 22);
+        assert_eq!(word_search.generate_string().lines().count(), 5);
+    }
+
+    #[test]
+    fn test_word_search_directions() {
+        let settings = WordSearchSettings { width: 6, height: 6, directions: vec![WordDirection::UpLeft], ..Default::default() };
+        let word_search = WordSearch::generate(&["one", "two", "three"], &settings).unwrap();
+
+        assert!(word_search.get_answer_key().get_words().iter().all(|word| word.direction == WordDirection::UpLeft));
+    }
+
+    #[test]
+    fn test_generate_word_search_errors() {
+        let settings = WordSearchSettings { width: 4, height: 4, ..Default::default() };
+        assert_eq!(WordSearch::generate(&["word"], &WordSearchSettings { width: 0, ..settings.clone() }), Err(WordSearchError::EmptyGrid));
+        assert_eq!(WordSearch::generate(&["word"], &WordSearchSettings { directions: vec![], ..settings.clone() }), Err(WordSearchError::EmptyGrid));
+        assert_eq!(WordSearch::generate(&["words"], &settings), Err(WordSearchError::WordDoesNotFit("words".to_owned())));
+        assert_eq!(WordSearch::generate(&[""], &settings), Err(WordSearchError::WordDoesNotFit("".to_owned())));
+        assert_eq!(WordSearch::generate(&["word"], &WordSearchSettings { letters: LetterSource::Alphabet("".to_owned()), ..settings.clone() }), Err(WordSearchError::NoLetters));
+        assert_eq!(WordSearch::generate(&["abcd", "efgh", "ijkl", "mnop", "qrst"], &WordSearchSettings { allow_overlaps: false, ..settings.clone() }), Err(WordSearchError::NotFound));
+        assert!(WordSearch::generate(&["abcd", "efgh", "ijkl", "mnop"], &WordSearchSettings { allow_overlaps: false, directions: vec![WordDirection::Right], ..settings }).is_ok());
+    }
+}